@@ -20,6 +20,7 @@ const permissionOptions: { id: AdminPermission; label: string }[] = [
   { id: 'audit_compliance', label: 'Audit & Compliance Monitoring' },
   { id: 'admin_request_creation', label: 'Create Admin Add/Remove Requests' },
   { id: 'admin_request_approval', label: 'Approve/Reject Admin Requests' },
+  { id: 'emergency_override', label: 'Emergency Override of Operating Windows' },
   { id: 'system_administration', label: 'System Administration' },
   { id: 'platform_governance', label: 'Platform Governance (Full Access)' }
 ];
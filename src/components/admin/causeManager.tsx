@@ -119,10 +119,14 @@ export function CauseManager({
 
   const handleReject = async (key: string) => {
     try {
+      const reviewNotes = window.prompt('Reason for rejecting this cause:')?.trim();
+      if (!reviewNotes) {
+        return;
+      }
       setRejectingId(key);
       const userId = user?.key || 'unknown';
       const userName = 'Admin';
-      await rejectCause(key, userId, userName);
+      await rejectCause(key, reviewNotes, userId, userName);
       await loadCauses();
     } catch (error) {
       console.error('Error rejecting cause:', error);
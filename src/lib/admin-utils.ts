@@ -14,6 +14,7 @@ type AdminPermission =
   | 'audit_compliance'     // Full audit access and compliance monitoring
   | 'admin_request_creation' // Create requests to add/remove admins (requires approval)
   | 'admin_request_approval' // Approve/reject admin add/remove requests
+  | 'emergency_override'   // Act outside configured operating windows (logged)
   | 'platform_governance'; // Supreme administrative authority
 
 // Digital platform roles with Islamic-inspired naming
@@ -39,6 +40,9 @@ interface AdminUser {
   deleted?: boolean;
   deletedAt?: number;
   deletedBy?: string;
+  restoredAt?: number;
+  restoredBy?: string;
+  expiresAt?: number; // End of a time-bound grant (ms)
 }
 
 interface AdminDoc extends Doc<AdminUser> {
@@ -67,6 +71,10 @@ interface AdminRequest {
   reviewedBy?: string; // Platform Admin who approved/rejected
   reviewedAt?: number;
   reviewNotes?: string; // Platform Admin's notes
+  grantExpiresAt?: number; // Makes the granted role time-bound (ms)
+  clearGrantExpiry?: boolean; // Update requests: removes the admin's current expiry
+  appliedAdminKey?: string; // Admin document changed when the approved request was applied
+  appliedAt?: number;
 }
 
 interface AdminRequestDoc extends Doc<AdminRequest> {
@@ -84,13 +92,13 @@ const ROLE_PERMISSIONS: Record<AdminRole, AdminPermission[]> = {
   waqf_manager: ['waqf_management', 'cause_management'], // Only waqf and cause creation/editing
   finance_officer: ['financial_oversight', 'audit_compliance'], // Only financial oversight
   compliance_officer: ['audit_compliance', 'financial_oversight', 'cause_approval', 'admin_request_creation'], // Can approve causes + request admin changes
-  platform_admin: ['platform_governance', 'system_administration', 'audit_compliance', 'financial_oversight', 'waqf_management', 'cause_management', 'cause_approval', 'content_moderation', 'user_support', 'admin_request_creation', 'admin_request_approval'] // Full access
+  platform_admin: ['platform_governance', 'system_administration', 'audit_compliance', 'financial_oversight', 'waqf_management', 'cause_management', 'cause_approval', 'content_moderation', 'user_support', 'admin_request_creation', 'admin_request_approval', 'emergency_override'] // Full access
 };
 
 const validateAdminUser = (data: Partial<AdminUser>): data is AdminUser => {
   const validPermissions = [
     'waqf_management', 'cause_management', 'cause_approval', 'user_support', 'financial_oversight',
-    'content_moderation', 'system_administration', 'audit_compliance', 'admin_request_creation', 'admin_request_approval', 'platform_governance'
+    'content_moderation', 'system_administration', 'audit_compliance', 'admin_request_creation', 'admin_request_approval', 'emergency_override', 'platform_governance'
   ];
   
   return (
@@ -212,26 +220,7 @@ const approveAdminRequest = async (
       throw new Error('Admin request has already been reviewed');
     }
 
-    // Execute the admin action FIRST (before updating request status)
-    console.log('🔧 Executing admin action for request:', request.data.type, 'for user:', request.data.targetUserId);
-    if (request.data.type === 'add') {
-      console.log('➕ Adding admin with role:', request.data.targetRole, 'permissions:', request.data.targetPermissions);
-      await addAdminDirectly(
-        request.data.targetUserId,
-        reviewedBy,
-        request.data.targetRole || 'support_agent',
-        request.data.targetUserEmail || '',
-        request.data.targetUserName || '',
-        request.data.targetPermissions
-      );
-      console.log('✅ Admin added successfully');
-    } else if (request.data.type === 'remove') {
-      console.log('➖ Removing admin');
-      await removeAdminDirectly(request.data.targetUserId, reviewedBy);
-      console.log('✅ Admin removed successfully');
-    }
-    
-    // Update request status AFTER successful admin action
+    // Only record the approval; the satellite applies the admin change itself
     const updatedRequestData: AdminRequest = {
       ...request.data,
       status: 'approved',
@@ -423,8 +412,7 @@ const addAdminDirectly = async (
     // Additional frontend fields
     userId,
     name: name || `User ${userId}`,
-    createdAt: Date.now(),
-    lastActive: Date.now()
+    createdAt: Date.now()
   };
   
   console.log('📋 Admin data to save:', adminData);
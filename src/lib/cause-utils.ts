@@ -7,6 +7,9 @@ import type { Cause, CauseDoc } from '@/types/waqfs';
 // Collection Name
 export const CAUSES_COLLECTION = 'causes';
 
+// Fields written together with a status change (cause_approval)
+const REVIEW_FIELDS = ['status', 'isActive', 'reviewedBy', 'reviewedAt', 'reviewNotes'];
+
 /**
  * Create a new cause
  */
@@ -88,7 +91,7 @@ export const updateCause = async (id: string, updates: Partial<Cause>, userId?:
       }
       
       // If other fields are being changed, need management permission
-      const hasNonStatusUpdates = Object.keys(updates).some(key => key !== 'status');
+      const hasNonStatusUpdates = Object.keys(updates).some(key => !REVIEW_FIELDS.includes(key));
       if (hasNonStatusUpdates && !await canManageCauses(userId)) {
         throw new Error('Permission denied: Only Waqf Managers can edit cause details');
       }
@@ -306,132 +309,72 @@ export const deactivateCause = async (id: string) => {
 };
 
 /**
- * Approve a cause
+ * Record a review decision. The satellite requires reviewedBy to be the caller's principal.
  */
-export const approveCause = async (id: string, userId?: string, userName?: string) => {
-  try {
-    // Check if user has cause approval permission
-    if (userId && !await canApproveCauses(userId)) {
-      throw new Error('Permission denied: Requires cause approval privileges');
-    }
-    
-    const cause = await getCause(id);
-    if (!cause) {
-      throw new Error('Cause not found');
-    }
-    
-    await updateCause(id, { status: 'approved', isActive: true }, userId, userName);
-    
-    // Log the approval activity if user info is provided
-    if (userId && userName) {
-      await logActivity(
-        'cause_approved',
-        userId,
-        userName,
-        {
-          targetId: id,
-          targetName: cause.name,
-          status: 'approved'
-        }
-      );
-    }
-  } catch (error) {
-    console.error('Error approving cause:', error);
-    throw new Error(`Failed to approve cause ${id}`);
+const reviewCause = async (
+  id: string,
+  status: 'approved' | 'rejected' | 'suspended',
+  reviewNotes: string | undefined,
+  userId?: string,
+  userName?: string
+) => {
+  // Check if user has cause approval permission
+  if (userId && !await canApproveCauses(userId)) {
+    throw new Error('Permission denied: Requires cause approval privileges');
   }
-};
-
-/**
- * Reject a cause
- */
-export const rejectCause = async (id: string, userId?: string, userName?: string) => {
-  try {
-    // Check if user has cause approval permission
-    if (userId && !await canApproveCauses(userId)) {
-      throw new Error('Permission denied: Requires cause approval privileges');
-    }
-    
-    const cause = await getCause(id);
-    if (!cause) {
-      throw new Error('Cause not found');
-    }
-    
-    await updateCause(id, { status: 'rejected', isActive: false }, userId, userName);
-    
-    // Log the rejection activity if user info is provided
-    if (userId && userName) {
-      await logActivity(
-        'cause_rejected',
-        userId,
-        userName,
-        {
-          targetId: id,
-          targetName: cause.name,
-          status: 'rejected'
-        }
-      );
-    }
-  } catch (error) {
-    console.error('Error rejecting cause:', error);
-    throw new Error(`Failed to reject cause ${id}`);
+  
+  if (!userId) {
+    throw new Error('Reviewer principal is required to review a cause');
   }
+  
+  const cause = await getCause(id);
+  if (!cause) {
+    throw new Error('Cause not found');
+  }
+  
+  // updateCause logs the status change activity
+  await updateCause(id, {
+    status,
+    isActive: status === 'approved',
+    reviewedBy: userId,
+    reviewedAt: new Date().toISOString(),
+    reviewNotes
+  }, userId, userName);
 };
 
 /**
- * Update cause funds raised
+ * Approve a cause
  */
-export const updateCauseFunds = async (id: string, amount: number) => {
+export const approveCause = async (id: string, userId?: string, userName?: string, reviewNotes?: string) => {
   try {
-    const cause = await getCause(id);
-    if (!cause) {
-      throw new Error('Cause not found');
-    }
-    
-    await updateCause(id, {
-      fundsRaised: (cause.fundsRaised || 0) + amount
-    });
+    await reviewCause(id, 'approved', reviewNotes, userId, userName);
   } catch (error) {
-    console.error('Error updating cause funds:', error);
-    throw new Error(`Failed to update funds for cause ${id}`);
+    console.error('Error approving cause:', error);
+    throw new Error(`Failed to approve cause ${id}`);
   }
 };
 
 /**
- * Increment cause followers
+ * Reject a cause (review notes are required)
  */
-export const incrementCauseFollowers = async (id: string) => {
+export const rejectCause = async (id: string, reviewNotes: string, userId?: string, userName?: string) => {
   try {
-    const cause = await getCause(id);
-    if (!cause) {
-      throw new Error('Cause not found');
-    }
-    
-    await updateCause(id, {
-      followers: (cause.followers || 0) + 1
-    });
+    await reviewCause(id, 'rejected', reviewNotes, userId, userName);
   } catch (error) {
-    console.error('Error incrementing cause followers:', error);
-    throw new Error(`Failed to increment followers for cause ${id}`);
+    console.error('Error rejecting cause:', error);
+    throw new Error(`Failed to reject cause ${id}`);
   }
 };
 
 /**
- * Decrement cause followers
+ * Suspend an approved cause (review notes are required)
  */
-export const decrementCauseFollowers = async (id: string) => {
+export const suspendCause = async (id: string, reviewNotes: string, userId?: string, userName?: string) => {
   try {
-    const cause = await getCause(id);
-    if (!cause) {
-      throw new Error('Cause not found');
-    }
-    
-    const newFollowers = Math.max(0, (cause.followers || 0) - 1);
-    await updateCause(id, {
-      followers: newFollowers
-    });
+    await reviewCause(id, 'suspended', reviewNotes, userId, userName);
   } catch (error) {
-    console.error('Error decrementing cause followers:', error);
-    throw new Error(`Failed to decrement followers for cause ${id}`);
+    console.error('Error suspending cause:', error);
+    throw new Error(`Failed to suspend cause ${id}`);
   }
 };
 
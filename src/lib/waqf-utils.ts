@@ -164,7 +164,8 @@ export const recordDonation = async (donation: Omit<Donation, 'id'>, userId?: st
       data: {
         ...donation,
         id,
-        status: 'completed',
+        // Finance admins confirm the payment as completed, which also applies sponsor matching
+        status: 'pending',
         date: donation.date || new Date().toISOString()
       }
     }
@@ -1,7 +1,14 @@
+use crate::store_utils::set_doc_data;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
 use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext};
 use junobuild_utils::decode_doc_data;
 
+pub const ACTIVITY_LOGS_COLLECTION: &str = "activity_logs";
+
+// Sequence number keeping keys unique for logs written within the same call
+static LOG_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
 // Activity log structure for WaqfProtocol - simplified and focused
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ActivityLog {
@@ -142,3 +149,34 @@ pub fn handle_activity_log_changes(context: OnSetDocContext) -> std::result::Res
     
     Ok(())
 }
+
+// Write an activity log entry from the satellite (e.g. from hooks and timers)
+pub fn record_activity_log(
+    action: &str,
+    category: &str,
+    level: &str,
+    user_id: Option<String>,
+    details: String,
+    resource_type: Option<&str>,
+    resource_id: Option<&str>,
+) -> std::result::Result<(), String> {
+    let now = ic_cdk::api::time();
+    let id = format!("sys_{}_{}", now, LOG_SEQUENCE.fetch_add(1, Ordering::Relaxed));
+    
+    let log = ActivityLog {
+        id: id.clone(),
+        action: action.to_string(),
+        category: category.to_string(),
+        level: level.to_string(),
+        user_id,
+        user_email: None,
+        details: Some(details),
+        resource_id: resource_id.map(|r| r.to_string()),
+        resource_type: resource_type.map(|r| r.to_string()),
+        timestamp: now / 1_000_000, // Convert to milliseconds
+    };
+    
+    validate_activity_log_data(&log)?;
+    
+    set_doc_data(ACTIVITY_LOGS_COLLECTION, &id, &log)
+}
@@ -0,0 +1,173 @@
+use crate::authz::require_permission;
+use crate::operating_window_utils::{validate_operating_windows_policy, OperatingWindowsPolicy, OPERATING_WINDOWS_KEY};
+use crate::rate_limit_utils::{validate_rate_limits_config, RateLimitsConfig, RATE_LIMITS_KEY};
+use crate::role_hooks::{
+    get_role_permissions, COMPLIANCE_OFFICER_ROLE, CONTENT_MODERATOR_ROLE, FINANCE_OFFICER_ROLE,
+    PLATFORM_ADMIN_ROLE, SUPPORT_AGENT_ROLE, WAQF_MANAGER_ROLE,
+};
+use crate::store_utils::get_doc_data;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext};
+use junobuild_utils::decode_doc_data;
+
+// Admin governance settings, one document per setting, editable by platform admins.
+// Settings missing from the collection fall back to the defaults below.
+pub const ADMIN_CONFIG_COLLECTION: &str = "admin_config";
+pub const ROLE_LIMITS_KEY: &str = "role_limits";
+pub const ADMIN_INACTIVITY_KEY: &str = "admin_inactivity";
+
+// Default maximum number of active admins per built-in role.
+// Custom roles are capped at MAX_ROLE_LIMIT unless configured.
+const DEFAULT_ROLE_LIMITS: &[(&str, u32)] = &[
+    (PLATFORM_ADMIN_ROLE, 3),
+    (COMPLIANCE_OFFICER_ROLE, 5),
+    (FINANCE_OFFICER_ROLE, 10),
+    (WAQF_MANAGER_ROLE, 20),
+    (CONTENT_MODERATOR_ROLE, 50),
+    (SUPPORT_AGENT_ROLE, 100),
+];
+
+const MAX_ROLE_LIMIT: u32 = 10_000;
+
+// Admins inactive for this many days are deactivated (0 disables the check).
+// Off until configured: lastActive is only recorded from permission-checked calls and
+// touch_admin_activity, so admins who have not used the platform since have no activity yet.
+const DEFAULT_INACTIVE_AFTER_DAYS: u32 = 0;
+const MIN_INACTIVE_AFTER_DAYS: u32 = 7;
+const MAX_INACTIVE_AFTER_DAYS: u32 = 3650;
+
+// Headcount caps, keyed by role name (e.g. "platform_admin")
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RoleLimitsConfig {
+    pub limits: HashMap<String, u32>,
+    #[serde(rename = "updatedBy")]
+    pub updated_by: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,                 // Milliseconds
+}
+
+// Automatic deactivation of inactive admins
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminInactivityConfig {
+    #[serde(rename = "inactiveAfterDays")]
+    pub inactive_after_days: u32,        // 0 disables automatic deactivation
+    #[serde(rename = "updatedBy")]
+    pub updated_by: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,                 // Milliseconds
+}
+
+// Number of days without activity after which an admin is deactivated, None if disabled
+pub fn get_inactive_after_days() -> std::result::Result<Option<u32>, String> {
+    let config: Option<AdminInactivityConfig> = get_doc_data(ADMIN_CONFIG_COLLECTION, ADMIN_INACTIVITY_KEY)?;
+
+    let days = config.map(|config| config.inactive_after_days).unwrap_or(DEFAULT_INACTIVE_AFTER_DAYS);
+
+    Ok(if days == 0 { None } else { Some(days) })
+}
+
+// Maximum number of active admins for a role
+pub fn get_role_limit(role: &str) -> std::result::Result<u32, String> {
+    let config: Option<RoleLimitsConfig> = get_doc_data(ADMIN_CONFIG_COLLECTION, ROLE_LIMITS_KEY)?;
+
+    if let Some(limit) = config.and_then(|config| config.limits.get(role).copied()) {
+        return Ok(limit);
+    }
+
+    Ok(DEFAULT_ROLE_LIMITS.iter()
+        .find(|(r, _)| *r == role)
+        .map(|(_, limit)| *limit)
+        .unwrap_or(MAX_ROLE_LIMIT))
+}
+
+fn validate_role_limits_config(config: &RoleLimitsConfig, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    for (role, limit) in &config.limits {
+        if get_role_permissions(role)?.is_none() {
+            return Err(format!("Unknown role in role limits: {}", role));
+        }
+
+        if *limit > MAX_ROLE_LIMIT {
+            return Err(format!("Role limit for {} must be at most {}", role, MAX_ROLE_LIMIT));
+        }
+    }
+
+    // The platform cannot be left without room for a platform admin
+    if config.limits.get(PLATFORM_ADMIN_ROLE) == Some(&0) {
+        return Err("Role limit for platform_admin must be at least 1".into());
+    }
+
+    validate_updated_by(&config.updated_by, config.updated_at, context)
+}
+
+fn validate_inactivity_config(config: &AdminInactivityConfig, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    let days = config.inactive_after_days;
+    if days != 0 && !(MIN_INACTIVE_AFTER_DAYS..=MAX_INACTIVE_AFTER_DAYS).contains(&days) {
+        return Err(format!(
+            "Inactivity threshold must be 0 (disabled) or between {} and {} days",
+            MIN_INACTIVE_AFTER_DAYS, MAX_INACTIVE_AFTER_DAYS
+        ));
+    }
+
+    validate_updated_by(&config.updated_by, config.updated_at, context)
+}
+
+fn validate_updated_by(updated_by: &str, updated_at: u64, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    if updated_by != context.caller.to_text() {
+        return Err("Updated by must be the caller".into());
+    }
+
+    if updated_at == 0 {
+        return Err("Updated at timestamp is required".into());
+    }
+
+    Ok(())
+}
+
+// Main assertion function for admin config operations
+pub fn assert_admin_config_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
+    require_permission(&context.caller, "platform_governance")
+        .map_err(|_| "Only platform admins can change admin settings (requires platform_governance permission)".to_string())?;
+
+    match context.data.key.as_str() {
+        ROLE_LIMITS_KEY => {
+            let config: RoleLimitsConfig = decode_doc_data(&context.data.data.proposed.data)
+                .map_err(|e| format!("Invalid role limits data structure: {}", e))?;
+            validate_role_limits_config(&config, &context)
+        },
+        ADMIN_INACTIVITY_KEY => {
+            let config: AdminInactivityConfig = decode_doc_data(&context.data.data.proposed.data)
+                .map_err(|e| format!("Invalid admin inactivity data structure: {}", e))?;
+            validate_inactivity_config(&config, &context)
+        },
+        RATE_LIMITS_KEY => {
+            let config: RateLimitsConfig = decode_doc_data(&context.data.data.proposed.data)
+                .map_err(|e| format!("Invalid rate limits data structure: {}", e))?;
+            validate_rate_limits_config(&config)?;
+            validate_updated_by(&config.updated_by, config.updated_at, &context)
+        },
+        OPERATING_WINDOWS_KEY => {
+            let policy: OperatingWindowsPolicy = decode_doc_data(&context.data.data.proposed.data)
+                .map_err(|e| format!("Invalid operating windows data structure: {}", e))?;
+            validate_operating_windows_policy(&policy)?;
+            validate_updated_by(&policy.updated_by, policy.updated_at, &context)
+        },
+        key => Err(format!("Unknown admin setting: {}", key)),
+    }
+}
+
+// Deleting a setting restores its defaults
+pub fn assert_admin_config_deletion(context: AssertDeleteDocContext) -> std::result::Result<(), String> {
+    require_permission(&context.caller, "platform_governance")
+        .map_err(|_| "Only platform admins can change admin settings (requires platform_governance permission)".to_string())
+}
+
+// Handle admin config changes (logging)
+pub fn handle_admin_config_changes(context: OnSetDocContext) -> std::result::Result<(), String> {
+    ic_cdk::println!(
+        "IMPORTANT: Admin setting '{}' changed by {}",
+        context.data.key, context.caller.to_text()
+    );
+
+    Ok(())
+}
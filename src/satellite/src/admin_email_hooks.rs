@@ -0,0 +1,115 @@
+use crate::admin_hooks::AdminUser;
+use crate::authz::ADMINS_COLLECTION;
+use crate::store_utils::{delete_doc_data, get_doc_data, is_satellite_caller, list_doc_data, set_doc_data};
+use serde::{Deserialize, Serialize};
+use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext};
+
+// Case-insensitive email -> admin key index, maintained by the admin hooks.
+// Documents are keyed by the lowercased email.
+pub const ADMIN_EMAILS_COLLECTION: &str = "admin_emails";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminEmailEntry {
+    pub email: String,                   // Email as written on the admin document
+    #[serde(rename = "adminKey")]
+    pub admin_key: String,               // Key of the admin document
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,                 // Milliseconds
+}
+
+fn email_key(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+// Key of the admin document using an email, if any
+pub fn find_admin_key_by_email(email: &str) -> std::result::Result<Option<String>, String> {
+    let entry: Option<AdminEmailEntry> = get_doc_data(ADMIN_EMAILS_COLLECTION, &email_key(email))?;
+
+    Ok(entry.map(|entry| entry.admin_key))
+}
+
+// Reject an email already used by another admin document
+pub fn validate_email_uniqueness(email: &str, admin_key: &str) -> std::result::Result<(), String> {
+    match find_admin_key_by_email(email)? {
+        Some(existing) if existing != admin_key => {
+            Err(format!("Admin with email '{}' already exists", email))
+        },
+        _ => Ok(()),
+    }
+}
+
+// Point an email at an admin document, dropping the entry of its previous email
+pub fn update_email_index(admin_key: &str, email: &str, previous_email: Option<&str>) -> std::result::Result<(), String> {
+    if let Some(previous_email) = previous_email {
+        if email_key(previous_email) != email_key(email) {
+            remove_email_index(admin_key, previous_email)?;
+        }
+    }
+
+    let entry = AdminEmailEntry {
+        email: email.to_string(),
+        admin_key: admin_key.to_string(),
+        updated_at: ic_cdk::api::time() / 1_000_000, // Convert to milliseconds
+    };
+
+    set_doc_data(ADMIN_EMAILS_COLLECTION, &email_key(email), &entry)
+}
+
+// Drop an email entry, if it still points at the given admin document
+pub fn remove_email_index(admin_key: &str, email: &str) -> std::result::Result<(), String> {
+    if find_admin_key_by_email(email)?.as_deref() == Some(admin_key) {
+        delete_doc_data(ADMIN_EMAILS_COLLECTION, &email_key(email))?;
+    }
+
+    Ok(())
+}
+
+// Rebuild the index from the admins collection. Returns the number of entries written.
+// Fails without writing anything if two admin documents share an email.
+pub fn rebuild_email_index() -> std::result::Result<u32, String> {
+    let admins: Vec<(String, AdminUser)> = list_doc_data(ADMINS_COLLECTION)?;
+
+    let mut seen: Vec<(String, String)> = Vec::with_capacity(admins.len());
+    for (key, admin) in &admins {
+        let email = email_key(&admin.email);
+        if let Some((_, other)) = seen.iter().find(|(e, _)| *e == email) {
+            return Err(format!("Admins {} and {} share the email '{}'", other, key, admin.email));
+        }
+        seen.push((email, key.clone()));
+    }
+
+    // Drop entries of emails no longer used by any admin
+    let entries: Vec<(String, AdminEmailEntry)> = list_doc_data(ADMIN_EMAILS_COLLECTION)?;
+    for (email, _) in entries {
+        if !seen.iter().any(|(e, _)| *e == email) {
+            delete_doc_data(ADMIN_EMAILS_COLLECTION, &email)?;
+        }
+    }
+
+    let mut written = 0;
+    for (key, admin) in &admins {
+        update_email_index(key, &admin.email, None)?;
+        written += 1;
+    }
+
+    ic_cdk::println!("Admin email index rebuilt: {} entries", written);
+
+    Ok(written)
+}
+
+// The index is only written by the satellite
+pub fn assert_admin_email_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
+    if !is_satellite_caller(&context.caller) {
+        return Err("The admin email index is maintained by the platform and cannot be written directly".into());
+    }
+
+    Ok(())
+}
+
+pub fn assert_admin_email_deletion(context: AssertDeleteDocContext) -> std::result::Result<(), String> {
+    if !is_satellite_caller(&context.caller) {
+        return Err("The admin email index is maintained by the platform and cannot be changed directly".into());
+    }
+
+    Ok(())
+}
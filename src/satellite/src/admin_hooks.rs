@@ -1,40 +1,21 @@
+use crate::activity_log_hooks::record_activity_log;
+use crate::admin_email_hooks::{remove_email_index, update_email_index, validate_email_uniqueness};
+use crate::admin_config_hooks::get_role_limit;
+use crate::authz::{get_caller_permissions, has_permission, require_permission, ADMINS_COLLECTION};
+use crate::operating_window_utils::{validate_operating_window, ADMIN_CHANGES};
+use crate::role_hooks::{get_role_permissions, COMPLIANCE_OFFICER_ROLE, PLATFORM_ADMIN_REQUIRED_PERMISSIONS, PLATFORM_ADMIN_ROLE};
+use crate::store_utils::{delete_doc_data, get_doc_data, is_satellite_caller, list_doc_data, set_doc_data};
+use candid::Principal;
 use serde::{Deserialize, Serialize};
-use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext};
+use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnDeleteDocContext, OnSetDocContext};
 use junobuild_utils::decode_doc_data;
 
-// Updated roles to match frontend exactly
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
-pub enum AdminRole {
-    #[serde(rename = "support_agent")]
-    SupportAgent,
-    #[serde(rename = "content_moderator")]
-    ContentModerator,
-    #[serde(rename = "waqf_manager")]
-    WaqfManager,
-    #[serde(rename = "finance_officer")]
-    FinanceOfficer,
-    #[serde(rename = "compliance_officer")]
-    ComplianceOfficer,
-    #[serde(rename = "platform_admin")]
-    PlatformAdmin,
-}
-
-// Updated permissions to match frontend exactly
-const ROLE_PERMISSIONS: &[(AdminRole, &[&str])] = &[
-    (AdminRole::SupportAgent, &["user_support"]),
-    (AdminRole::ContentModerator, &["content_moderation", "user_support"]),
-    (AdminRole::WaqfManager, &["waqf_management", "cause_management"]),
-    (AdminRole::FinanceOfficer, &["financial_oversight", "audit_compliance"]),
-    (AdminRole::ComplianceOfficer, &["audit_compliance", "financial_oversight", "cause_approval", "admin_request_creation"]),
-    (AdminRole::PlatformAdmin, &["platform_governance", "system_administration", "audit_compliance", "financial_oversight", "waqf_management", "cause_management", "cause_approval", "content_moderation", "user_support", "admin_request_creation", "admin_request_approval"]),
-];
-
 // AdminUser structure to match frontend exactly
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AdminUser {
     // Core fields expected by both frontend and backend
     pub email: String,
-    pub role: AdminRole,
+    pub role: String,                    // Key of a document in the roles collection
     pub permissions: Vec<String>,
     
     // Backend compatibility fields
@@ -58,6 +39,23 @@ pub struct AdminUser {
     pub deleted_at: Option<u64>,
     #[serde(rename = "deletedBy")]
     pub deleted_by: Option<String>,
+    #[serde(rename = "restoredAt")]
+    pub restored_at: Option<u64>,
+    #[serde(rename = "restoredBy")]
+    pub restored_by: Option<String>,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: Option<u64>,         // Milliseconds, end of a time-bound grant
+}
+
+impl AdminUser {
+    pub fn is_deleted(&self) -> bool {
+        self.deleted == Some(true)
+    }
+    
+    // Whether a time-bound grant has ended (now in milliseconds)
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.map(|expires_at| expires_at <= now).unwrap_or(false)
+    }
 }
 
 // Basic validation function
@@ -67,28 +65,24 @@ fn validate_admin_data(admin: &AdminUser) -> std::result::Result<(), String> {
         return Err("Invalid admin email format".into());
     }
     
-    // 2. Validate role-permission consistency  
-    let allowed_perms = ROLE_PERMISSIONS
-        .iter()
-        .find(|(role, _)| role == &admin.role)
-        .map(|(_, perms)| perms)
-        .ok_or_else(|| format!("Invalid role: {:?}", admin.role))?;
+    // 2. Validate role-permission consistency against the roles collection
+    let allowed_perms = get_role_permissions(&admin.role)?
+        .ok_or_else(|| format!("Unknown role: {}", admin.role))?;
     
     // Validate each permission
     for perm in &admin.permissions {
-        if !allowed_perms.contains(&perm.as_str()) {
+        if !allowed_perms.contains(perm) {
             return Err(format!(
-                "Permission '{}' not allowed for role {:?}. Allowed permissions: {:?}", 
+                "Permission '{}' not allowed for role {}. Allowed permissions: {:?}", 
                 perm, admin.role, allowed_perms
             ));
         }
     }
     
     // 3. Special validation for platform admin
-    if admin.role == AdminRole::PlatformAdmin {
-        let required_perms = ["platform_governance", "admin_request_approval"];
-        for req_perm in &required_perms {
-            if !admin.permissions.contains(&req_perm.to_string()) {
+    if admin.role == PLATFORM_ADMIN_ROLE {
+        for req_perm in PLATFORM_ADMIN_REQUIRED_PERMISSIONS {
+            if !admin.permissions.iter().any(|p| p == req_perm) {
                 return Err(format!(
                     "Platform admin must have permission: {}", req_perm
                 ));
@@ -147,102 +141,457 @@ fn is_valid_email(email: &str) -> bool {
     local.chars().all(valid_local_chars) && domain.chars().all(valid_domain_chars)
 }
 
+// Admin documents are keyed by the admin's principal, which user_id repeats
+fn validate_admin_identity(admin: &AdminUser, key: &str) -> std::result::Result<(), String> {
+    let principal = Principal::from_text(key)
+        .map_err(|_| format!("Admin document key must be the admin's principal, got '{}'. Legacy documents can be moved with the migrate_admin_document_keys method.", key))?;
+    
+    if principal.to_text() != key || principal == Principal::anonymous() {
+        return Err(format!("Admin document key '{}' is not a valid admin principal", key));
+    }
+    
+    match admin.user_id.as_deref() {
+        Some(user_id) if user_id == key => Ok(()),
+        Some(user_id) => Err(format!("userId '{}' must match the admin document key '{}'", user_id, key)),
+        None => Err("userId is required and must be the admin's principal".into()),
+    }
+}
+
 // Business rules validation function
 fn validate_admin_business_rules(admin: &AdminUser, context: &AssertSetDocContext) -> std::result::Result<(), String> {
-    // 1. Time restriction validation (business hours)
-    validate_business_hours()?;
+    // 1. Time restriction validation (operating windows)
+    validate_operating_window(&context.caller, ADMIN_CHANGES, &context.data.key)?;
+    
+    let previous: Option<AdminUser> = match &context.data.data.current {
+        Some(current_doc) => Some(decode_doc_data(&current_doc.data)
+            .map_err(|e| format!("Cannot decode current admin data: {}", e))?),
+        None => None,
+    };
+    
+    // 2. Privilege escalation guard
+    validate_privilege_escalation(admin, previous.as_ref(), &context.data.key, &context.caller)?;
     
-    // 2. Email uniqueness check (for new admins)
-    if context.data.data.current.is_none() {
-        validate_email_uniqueness(&admin.email)?;
+    // 3. lastActive is maintained by the satellite
+    if !is_satellite_caller(&context.caller) && admin.last_active != previous.as_ref().and_then(|p| p.last_active) {
+        return Err("lastActive is recorded by the platform and cannot be set directly".into());
     }
     
-    // 3. Role count limits
-    validate_role_limits(&admin.role)?;
+    // 4. Time-bound grant validation
+    validate_grant_expiry(admin, previous.as_ref())?;
     
-    // 4. Special permissions validation for sensitive roles
-    if matches!(admin.role, AdminRole::PlatformAdmin | AdminRole::ComplianceOfficer) {
+    // 5. Soft-delete and restore consistency
+    validate_soft_delete_transition(admin, previous.as_ref(), &context.caller)?;
+    
+    // 6. Email uniqueness check (for new admins and email changes)
+    if previous.as_ref().map(|p| !p.email.eq_ignore_ascii_case(&admin.email)).unwrap_or(true) {
+        validate_email_uniqueness(&admin.email, &context.data.key)?;
+    }
+    
+    // 7. Role count limits (for new admins, role changes and reactivations)
+    let counted = |a: &AdminUser| a.active && !a.is_deleted();
+    let joins_role = previous.as_ref()
+        .map(|p| p.role != admin.role || !counted(p))
+        .unwrap_or(true);
+    if counted(admin) && joins_role {
+        validate_role_limits(&admin.role, &context.data.key)?;
+    }
+    
+    // 8. Special permissions validation for sensitive roles
+    if admin.role == PLATFORM_ADMIN_ROLE || admin.role == COMPLIANCE_OFFICER_ROLE {
         validate_sensitive_role_requirements(admin, context)?;
     }
     
     Ok(())
 }
 
-// Validate business hours (9 AM - 6 PM UTC, Monday-Friday)
-fn validate_business_hours() -> std::result::Result<(), String> {
-    // For testing, we'll disable this check
-    // In production, you would implement time zone logic
-    // 
-    // let current_time = ic_cdk::api::time();
-    // let hour = ((current_time / 1_000_000_000) % 86400) / 3600;
-    // let day_of_week = ((current_time / 1_000_000_000) / 86400 + 4) % 7; // Monday = 0
-    // 
-    // if day_of_week >= 5 || hour < 9 || hour >= 18 {
-    //     return Err("Admin operations are only allowed during business hours (9 AM - 6 PM UTC, Monday-Friday)".into());
-    // }
+// Callers can only write admins whose role and permissions are within their own permissions,
+// both before and after the change. Admins may update their own document, but never their
+// role, permissions, active status or expiry.
+fn validate_privilege_escalation(admin: &AdminUser, previous: Option<&AdminUser>, key: &str, caller: &Principal) -> std::result::Result<(), String> {
+    if is_satellite_caller(caller) {
+        return Ok(());
+    }
+    
+    if key == caller.to_text() {
+        let unchanged_privileges = previous.map(|p| {
+            p.role == admin.role
+                && admin.permissions.iter().all(|perm| p.permissions.contains(perm))
+                && (p.active || !admin.active)
+                && p.expires_at == admin.expires_at
+        }).unwrap_or(false);
+        
+        if !unchanged_privileges {
+            return Err("Admins cannot create their own account or change their own role, permissions, active status or expiry".into());
+        }
+        
+        return Ok(());
+    }
+    
+    let caller_permissions = get_caller_permissions(caller)?;
+    
+    if let Some(previous) = previous {
+        validate_caller_covers(previous, &caller_permissions)?;
+    }
+    validate_caller_covers(admin, &caller_permissions)
+}
+
+// Reject an admin whose role or permissions exceed the caller's permissions.
+// Platform admin accounts always require platform_governance.
+fn validate_caller_covers(admin: &AdminUser, caller_permissions: &[String]) -> std::result::Result<(), String> {
+    validate_caller_covers_role(&admin.role, caller_permissions)?;
+    
+    if let Some(missing) = admin.permissions.iter().find(|perm| !caller_permissions.contains(perm)) {
+        return Err(format!(
+            "Cannot manage a {} admin: permission '{}' is not among the caller's permissions",
+            admin.role, missing
+        ));
+    }
     
     Ok(())
 }
 
-// Validate email uniqueness (placeholder - would query existing admins)
-fn validate_email_uniqueness(_email: &str) -> std::result::Result<(), String> {
-    // For testing, we'll skip this check
-    // In production, you would query the admins collection
-    // to ensure email uniqueness
-    // 
-    // let existing_admins = get_all_admins().await?;
-    // if existing_admins.iter().any(|a| a.email == email) {
-    //     return Err(format!("Admin with email '{}' already exists", email));
-    // }
+fn validate_caller_covers_role(role: &str, caller_permissions: &[String]) -> std::result::Result<(), String> {
+    if role == PLATFORM_ADMIN_ROLE && !caller_permissions.iter().any(|p| p == "platform_governance") {
+        return Err("Only platform admins can manage Platform Admin accounts (requires platform_governance permission)".into());
+    }
+    
+    let role_permissions = get_role_permissions(role)?.unwrap_or_default();
+    
+    if let Some(missing) = role_permissions.iter().find(|perm| !caller_permissions.contains(perm)) {
+        return Err(format!(
+            "Cannot manage a {} admin: permission '{}' is not among the caller's permissions",
+            role, missing
+        ));
+    }
+    
+    Ok(())
+}
+
+// Same guard for changes carried out on the caller's behalf (e.g. approving an admin request)
+pub fn validate_caller_can_manage_role(caller: &Principal, role: &str) -> std::result::Result<(), String> {
+    if is_satellite_caller(caller) {
+        return Ok(());
+    }
+    
+    validate_caller_covers_role(role, &get_caller_permissions(caller)?)
+}
+
+// Time-bound grants: a new or changed expiry must lie in the future, and an expired admin
+// can only be reactivated together with a new expiry. Platform admin grants never expire,
+// so expiry cannot lock the platform out.
+fn validate_grant_expiry(admin: &AdminUser, previous: Option<&AdminUser>) -> std::result::Result<(), String> {
+    if admin.expires_at.is_none() {
+        return Ok(());
+    }
+    
+    if admin.role == PLATFORM_ADMIN_ROLE {
+        return Err("Platform Admin grants cannot expire".into());
+    }
+    
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    let expiry_changed = previous.map(|p| p.expires_at != admin.expires_at).unwrap_or(true);
+    if expiry_changed && admin.is_expired(now) {
+        return Err("expiresAt must be in the future".into());
+    }
+    
+    let reactivated = previous.map(|p| !p.active).unwrap_or(false) && admin.active;
+    if reactivated && admin.is_expired(now) {
+        return Err("This admin's grant has expired. Set a new expiresAt to reactivate it.".into());
+    }
     
     Ok(())
 }
 
-// Validate role count limits
-fn validate_role_limits(role: &AdminRole) -> std::result::Result<(), String> {
-    // Define maximum counts for each role
-    let _max_counts = match role {
-        AdminRole::PlatformAdmin => 3,   // Maximum 3 platform admins
-        AdminRole::ComplianceOfficer => 5, // Maximum 5 compliance officers
-        AdminRole::FinanceOfficer => 10,
-        AdminRole::WaqfManager => 20,
-        AdminRole::ContentModerator => 50,
-        AdminRole::SupportAgent => 100,
+// Soft-delete lifecycle: deleted, deleted_at and deleted_by are set together by the deleting caller,
+// stay untouched while the admin is deleted, and are cleared by a restore, which records who restored.
+// The satellite acts on behalf of others (e.g. approved requests), so it may record another principal.
+fn validate_soft_delete_transition(admin: &AdminUser, previous: Option<&AdminUser>, caller: &Principal) -> std::result::Result<(), String> {
+    let deletion_fields = (admin.deleted_at.is_some(), admin.deleted_by.is_some());
+    
+    if admin.is_deleted() {
+        if deletion_fields != (true, true) {
+            return Err("Deleted admins must have deleted, deletedAt and deletedBy set together".into());
+        }
+    } else if deletion_fields != (false, false) {
+        return Err("deletedAt and deletedBy can only be set when deleted is true".into());
+    }
+    
+    if admin.restored_at.is_some() != admin.restored_by.is_some() {
+        return Err("restoredAt and restoredBy must be set together".into());
+    }
+    
+    let caller_text = caller.to_text();
+    let recorded_as_caller = |recorded: &Option<String>| {
+        is_satellite_caller(caller) || recorded.as_deref() == Some(caller_text.as_str())
+    };
+    
+    let Some(previous) = previous else {
+        // The satellite may carry existing records over to a new document (key migration)
+        if is_satellite_caller(caller) {
+            return Ok(());
+        }
+        if admin.is_deleted() {
+            return Err("Admins cannot be created in a deleted state".into());
+        }
+        if admin.restored_at.is_some() {
+            return Err("New admins cannot carry restore information".into());
+        }
+        return Ok(());
     };
     
-    // For testing, we'll skip count validation
-    // In production, you would query the admins collection
-    // to count existing admins with this role
-    // 
-    // let current_count = count_admins_by_role(role).await?;
-    // if current_count >= max_counts {
-    //     return Err(format!("Maximum limit of {} {:?} admins reached", max_counts, role));
-    // }
+    match (previous.is_deleted(), admin.is_deleted()) {
+        // Soft-delete
+        (false, true) => {
+            if !recorded_as_caller(&admin.deleted_by) {
+                return Err("deletedBy must be the caller".into());
+            }
+            if admin.deleted_at == Some(0) {
+                return Err("deletedAt timestamp is required".into());
+            }
+        },
+        // Still deleted: the deletion record is immutable
+        (true, true) => {
+            if admin.deleted_at != previous.deleted_at || admin.deleted_by != previous.deleted_by {
+                return Err("The deletion record of a deleted admin cannot be changed".into());
+            }
+        },
+        // Restore
+        (true, false) => {
+            require_permission(caller, "platform_governance")
+                .map_err(|_| "Only platform admins can restore deleted admins (requires platform_governance permission)".to_string())?;
+            
+            if admin.restored_at.is_none() || admin.restored_at == previous.restored_at {
+                return Err("Restoring an admin requires a new restoredAt and restoredBy".into());
+            }
+            if !recorded_as_caller(&admin.restored_by) {
+                return Err("restoredBy must be the caller".into());
+            }
+        },
+        // Regular update: the restore record is immutable
+        (false, false) => {
+            if admin.restored_at != previous.restored_at || admin.restored_by != previous.restored_by {
+                return Err("restoredAt and restoredBy are only set when restoring an admin".into());
+            }
+        },
+    }
+    
+    Ok(())
+}
+
+// Validate role count limits against the configured caps, counting active, non-deleted admins
+// (a legacy document of the same principal is not counted twice)
+fn validate_role_limits(role: &str, admin_key: &str) -> std::result::Result<(), String> {
+    let max_count = get_role_limit(role)?;
+    
+    let admins: Vec<(String, AdminUser)> = list_doc_data(ADMINS_COLLECTION)?;
+    let current_count = admins.iter()
+        .filter(|(key, a)| key != admin_key && a.user_id.as_deref() != Some(admin_key))
+        .filter(|(_, a)| a.role == role && a.active && !a.is_deleted())
+        .count() as u32;
+    
+    if current_count >= max_count {
+        return Err(format!("Maximum limit of {} {} admins reached", max_count, role));
+    }
     
     Ok(())
 }
 
 // Validate sensitive role requirements
-fn validate_sensitive_role_requirements(admin: &AdminUser, _context: &AssertSetDocContext) -> std::result::Result<(), String> {
+fn validate_sensitive_role_requirements(admin: &AdminUser, context: &AssertSetDocContext) -> std::result::Result<(), String> {
     // For Platform Admin role, ensure proper authorization chain
-    if admin.role == AdminRole::PlatformAdmin {
-        // Ensure the requester has appropriate permissions
-        // This would typically check the calling user's permissions
-        // 
-        // let caller_permissions = get_caller_permissions(&context.caller).await?;
-        // if !caller_permissions.contains(&"admin_request_approval".to_string()) {
-        //     return Err("Only authorized users can create Platform Admin accounts".into());
-        // }
-    }
-    
-    // Ensure active status for sensitive roles
-    if !admin.active {
+    if admin.role == PLATFORM_ADMIN_ROLE && !has_permission(&context.caller, "admin_request_approval")? {
+        return Err("Only authorized users can create Platform Admin accounts (requires admin_request_approval permission)".into());
+    }
+    
+    // Ensure active status for sensitive roles (they may be deactivated later)
+    if context.data.data.current.is_none() && !admin.active && !is_satellite_caller(&context.caller) {
         return Err("Sensitive roles must be active upon creation".into());
     }
     
     Ok(())
 }
 
+fn is_active_platform_admin(admin: &AdminUser) -> bool {
+    admin.role == PLATFORM_ADMIN_ROLE && admin.active && !admin.is_deleted()
+}
+
+// Whether an admin document can be used to sign in: authorization looks admins up by
+// principal, so legacy documents (e.g. keyed by email) are not reachable until migrated
+fn is_principal_keyed(key: &str, admin: &AdminUser) -> bool {
+    Principal::from_text(key).map(|p| p.to_text() == key).unwrap_or(false)
+        && admin.user_id.as_deref() == Some(key)
+}
+
+// Active platform admins other than the given one who can actually sign in
+fn count_other_active_platform_admins(admin_key: &str) -> std::result::Result<usize, String> {
+    let admins: Vec<(String, AdminUser)> = list_doc_data(ADMINS_COLLECTION)?;
+    
+    Ok(admins.iter()
+        .filter(|(key, admin)| key != admin_key && is_active_platform_admin(admin) && is_principal_keyed(key, admin))
+        .count())
+}
+
+// Reject a change that would take away the last active platform admin
+// (deletion, deactivation, soft-deletion or demotion)
+fn validate_platform_admin_remains(admin_key: &str) -> std::result::Result<(), String> {
+    if count_other_active_platform_admins(admin_key)? == 0 {
+        return Err("Cannot remove the last active Platform Admin. Appoint another Platform Admin first.".into());
+    }
+    
+    Ok(())
+}
+
+// Bootstrap (fresh satellite) or lockout recovery: make a principal an active platform admin
+// when none is left. Bootstrap refuses once any admin document exists, recovery reuses the
+// principal's admin document if it exists (reactivating and restoring it). Both record a
+// critical audit log entry. Only callable from a controller-guarded endpoint.
+pub fn seed_platform_admin(controller: &Principal, user: &Principal, email: &str, name: Option<String>, bootstrap: bool) -> std::result::Result<(), String> {
+    if count_other_active_platform_admins("")? > 0 {
+        return Err("An active Platform Admin already exists".into());
+    }
+    
+    let is_bootstrap = list_doc_data::<AdminUser>(ADMINS_COLLECTION)?.is_empty();
+    if bootstrap && !is_bootstrap {
+        return Err("Admin documents already exist. Use recover_platform_admin_access instead.".into());
+    }
+    
+    let key = user.to_text();
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    let existing: Option<AdminUser> = get_doc_data(ADMINS_COLLECTION, &key)?;
+    let was_deleted = existing.as_ref().map(|admin| admin.is_deleted()).unwrap_or(false);
+    let permissions = get_role_permissions(PLATFORM_ADMIN_ROLE)?
+        .ok_or("The platform_admin role is not defined")?;
+    
+    let admin = match existing {
+        Some(existing) => AdminUser {
+            email: email.to_string(),
+            role: PLATFORM_ADMIN_ROLE.to_string(),
+            permissions: permissions.clone(),
+            active: true,
+            name: name.or(existing.name),
+            updated_at: Some(now),
+            updated_by: Some(controller.to_text()),
+            deleted: Some(false),
+            deleted_at: None,
+            deleted_by: None,
+            restored_at: if was_deleted { Some(now) } else { existing.restored_at },
+            restored_by: if was_deleted { Some(controller.to_text()) } else { existing.restored_by },
+            expires_at: None,
+            ..existing
+        },
+        None => AdminUser {
+            email: email.to_string(),
+            role: PLATFORM_ADMIN_ROLE.to_string(),
+            permissions,
+            created_by: controller.to_text(),
+            active: true,
+            user_id: Some(key.clone()),
+            name,
+            created_at: Some(now),
+            last_active: None,
+            updated_at: Some(now),
+            updated_by: Some(controller.to_text()),
+            deleted: Some(false),
+            deleted_at: None,
+            deleted_by: None,
+            restored_at: None,
+            restored_by: None,
+            expires_at: None,
+        },
+    };
+    
+    set_admin_data(&key, &admin)?;
+    
+    let (action, label) = if is_bootstrap {
+        ("platform_admin_bootstrapped", "BOOTSTRAPPED")
+    } else {
+        ("platform_admin_recovered", "RECOVERED")
+    };
+    
+    ic_cdk::println!("CRITICAL: Platform Admin {} - {} ({}) by controller {}", label, admin.email, key, controller.to_text());
+    
+    // Trap rather than return, so the admin is never kept without its audit entry
+    if let Err(e) = record_activity_log(
+        action,
+        "audit",
+        "critical",
+        Some(controller.to_text()),
+        format!("Platform Admin {} ({}) seeded by satellite controller {}", admin.email, key, controller.to_text()),
+        Some("admin"),
+        Some(&key),
+    ) {
+        ic_cdk::trap(format!("Platform Admin {} not seeded, audit log entry failed: {}", key, e));
+    }
+    
+    Ok(())
+}
+
+// Move admin documents keyed by anything other than the admin's principal (e.g. email-keyed
+// legacy documents) to their principal key, taken from user_id. Documents without a valid
+// user_id, or whose principal already has a document, are left in place and reported.
+// Returns the number of documents migrated. Only callable from a controller-guarded endpoint.
+pub fn migrate_admin_keys() -> std::result::Result<u32, String> {
+    let admins: Vec<(String, AdminUser)> = list_doc_data(ADMINS_COLLECTION)?;
+    let mut migrated = 0;
+    
+    for (key, admin) in admins {
+        if Principal::from_text(&key).map(|p| p.to_text() == key).unwrap_or(false) {
+            continue;
+        }
+        
+        let principal = match admin.user_id.as_deref().map(Principal::from_text) {
+            Some(Ok(principal)) if principal != Principal::anonymous() => principal.to_text(),
+            _ => {
+                ic_cdk::println!("Admin {} not migrated: userId is not a valid principal", key);
+                continue;
+            },
+        };
+        
+        if get_doc_data::<AdminUser>(ADMINS_COLLECTION, &principal)?.is_some() {
+            ic_cdk::println!("Admin {} not migrated: {} already has an admin document", key, principal);
+            continue;
+        }
+        
+        // Point the email at the new document first so the uniqueness check accepts it
+        update_email_index(&principal, &admin.email, None)?;
+        
+        let updated = AdminUser {
+            user_id: Some(principal.clone()),
+            ..admin
+        };
+        
+        if let Err(e) = set_doc_data(ADMINS_COLLECTION, &principal, &updated) {
+            update_email_index(&key, &updated.email, None)?;
+            ic_cdk::println!("Admin {} not migrated: {}", key, e);
+            continue;
+        }
+        
+        delete_doc_data(ADMINS_COLLECTION, &key)?;
+        migrated += 1;
+        
+        ic_cdk::println!("Admin document migrated: {} -> {}", key, principal);
+    }
+    
+    Ok(migrated)
+}
+
+// Write an admin document from the satellite. The admin assertion claims the email in the
+// index; the public set_doc rolls that back when the write fails, but internal writes do not,
+// so a failed write puts the index back as it was.
+pub fn set_admin_data(key: &str, admin: &AdminUser) -> std::result::Result<(), String> {
+    let previous: Option<AdminUser> = get_doc_data(ADMINS_COLLECTION, key)?;
+    
+    if let Err(e) = set_doc_data(ADMINS_COLLECTION, key, admin) {
+        match &previous {
+            Some(previous) => update_email_index(key, &previous.email, Some(&admin.email))?,
+            None => remove_email_index(key, &admin.email)?,
+        }
+        return Err(e);
+    }
+    
+    Ok(())
+}
+
 // Main assertion function for admin operations
 pub fn assert_admin_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
     // Decode admin data with proper error handling
@@ -251,10 +600,30 @@ pub fn assert_admin_operations(context: AssertSetDocContext) -> std::result::Res
     
     // Validate the admin data structure
     validate_admin_data(&admin)?;
+    validate_admin_identity(&admin, &context.data.key)?;
     
     // Business logic validation
     validate_admin_business_rules(&admin, &context)?;
     
+    let previous: Option<AdminUser> = match &context.data.data.current {
+        Some(current_doc) => Some(decode_doc_data(&current_doc.data)
+            .map_err(|e| format!("Cannot decode current admin data: {}", e))?),
+        None => None,
+    };
+    
+    // Never leave the platform without an active platform admin
+    if let Some(previous) = &previous {
+        if is_active_platform_admin(previous) && !is_active_platform_admin(&admin) {
+            validate_platform_admin_remains(&context.data.key)?;
+        }
+    }
+    
+    // Claim the email in the index as the very last step, once every check has passed.
+    // Doing it here rather than in on_set_doc (which runs later) means a second write racing
+    // for the same email already sees the entry. Internal writes go through set_admin_data,
+    // which releases the claim if the write fails.
+    update_email_index(&context.data.key, &admin.email, previous.as_ref().map(|p| p.email.as_str()))?;
+    
     Ok(())
 }
 
@@ -266,20 +635,21 @@ pub fn assert_admin_deletion(context: AssertDeleteDocContext) -> std::result::Re
     let admin_to_delete: AdminUser = decode_doc_data(&current_doc.data)
         .map_err(|e| format!("Cannot decode admin data for deletion: {}", e))?;
     
-    // Business hours validation
-    validate_business_hours()?;
+    // Operating window validation
+    validate_operating_window(&context.caller, ADMIN_CHANGES, &context.data.key)?;
+    
+    // Only admins covering the deleted admin's role and permissions may delete it
+    if !is_satellite_caller(&context.caller) {
+        validate_caller_covers(&admin_to_delete, &get_caller_permissions(&context.caller)?)?;
+    }
     
     // Prevent deletion of last Platform Admin
-    if admin_to_delete.role == AdminRole::PlatformAdmin {
-        // In production, you would check if this is the last Platform Admin
-        // let platform_admin_count = count_admins_by_role(&AdminRole::PlatformAdmin).await?;
-        // if platform_admin_count <= 1 {
-        //     return Err("Cannot delete the last Platform Admin".into());
-        // }
+    if is_active_platform_admin(&admin_to_delete) {
+        validate_platform_admin_remains(&context.data.key)?;
     }
     
     // Log critical deletion attempt
-    ic_cdk::println!("Admin deletion attempt: {} with role {:?}", 
+    ic_cdk::println!("Admin deletion attempt: {} with role {}", 
                     admin_to_delete.email, admin_to_delete.role);
     
     Ok(())
@@ -298,7 +668,7 @@ pub fn handle_admin_changes(context: OnSetDocContext) -> std::result::Result<(),
     
     // Enhanced logging for audit purposes
     ic_cdk::println!(
-        "Admin {}: {} - Role: {:?}, Permissions: {:?}, Active: {}, Email: {}", 
+        "Admin {}: {} - Role: {}, Permissions: {:?}, Active: {}, Email: {}", 
         operation_type,
         context.data.key,
         admin_data.role, 
@@ -308,11 +678,11 @@ pub fn handle_admin_changes(context: OnSetDocContext) -> std::result::Result<(),
     );
     
     // Log role-specific information
-    match admin_data.role {
-        AdminRole::PlatformAdmin => {
+    match admin_data.role.as_str() {
+        PLATFORM_ADMIN_ROLE => {
             ic_cdk::println!("CRITICAL: Platform Admin {} - {}", operation_type, admin_data.email);
         },
-        AdminRole::ComplianceOfficer => {
+        COMPLIANCE_OFFICER_ROLE => {
             ic_cdk::println!("IMPORTANT: Compliance Officer {} - {}", operation_type, admin_data.email);
         },
         _ => {}
@@ -326,3 +696,15 @@ pub fn handle_admin_changes(context: OnSetDocContext) -> std::result::Result<(),
     
     Ok(())
 }
+
+// Handle admin deletion (release the email)
+pub fn handle_admin_deletion(context: OnDeleteDocContext) -> std::result::Result<(), String> {
+    let Some(deleted_doc) = &context.data.data else {
+        return Ok(());
+    };
+    
+    let admin: AdminUser = decode_doc_data(&deleted_doc.data)
+        .map_err(|e| format!("Cannot decode deleted admin data: {}", e))?;
+    
+    remove_email_index(&context.data.key, &admin.email)
+}
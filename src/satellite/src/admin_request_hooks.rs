@@ -1,7 +1,17 @@
+use crate::activity_log_hooks::record_activity_log;
+use crate::admin_email_hooks::find_admin_key_by_email;
+use crate::admin_hooks::{set_admin_data, validate_caller_can_manage_role, AdminUser};
+use crate::authz::{has_permission, require_permission, ADMINS_COLLECTION};
+use crate::operating_window_utils::{validate_operating_window, ADMIN_CHANGES};
+use crate::role_hooks::{get_role_permissions, PLATFORM_ADMIN_ROLE};
+use crate::store_utils::{get_doc_data, is_satellite_caller, set_doc_data};
+use candid::Principal;
 use serde::{Deserialize, Serialize};
 use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext};
 use junobuild_utils::decode_doc_data;
 
+const ADMIN_REQUESTS_COLLECTION: &str = "admin_requests";
+
 // Admin request type enum - should match frontend
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub enum AdminRequestType {
@@ -26,23 +36,6 @@ pub enum AdminRequestStatus {
     Expired,
 }
 
-// Admin roles enum - reused from admin_hooks
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
-pub enum AdminRole {
-    #[serde(rename = "support_agent")]
-    SupportAgent,
-    #[serde(rename = "content_moderator")]
-    ContentModerator,
-    #[serde(rename = "waqf_manager")]
-    WaqfManager,
-    #[serde(rename = "finance_officer")]
-    FinanceOfficer,
-    #[serde(rename = "compliance_officer")]
-    ComplianceOfficer,
-    #[serde(rename = "platform_admin")]
-    PlatformAdmin,
-}
-
 // Admin request structure for validation
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AdminRequest {
@@ -52,8 +45,12 @@ pub struct AdminRequest {
     pub status: AdminRequestStatus,
     #[serde(rename = "targetAdminEmail")]
     pub target_admin_email: String,
+    #[serde(rename = "targetUserId")]
+    pub target_user_id: Option<String>,  // Principal of the admin to add (required for "add")
     #[serde(rename = "targetRole")]
-    pub target_role: AdminRole,
+    pub target_role: String,             // Key of a document in the roles collection
+    #[serde(rename = "targetPermissions")]
+    pub target_permissions: Option<Vec<String>>, // Defaults to every permission of the target role
     pub justification: String,
     #[serde(rename = "requestedBy")]
     pub requested_by: String,
@@ -67,6 +64,14 @@ pub struct AdminRequest {
     pub review_notes: Option<String>,
     #[serde(rename = "expiresAt")]
     pub expires_at: Option<u64>,
+    #[serde(rename = "grantExpiresAt")]
+    pub grant_expires_at: Option<u64>,   // Milliseconds, makes the granted role time-bound (add/update)
+    #[serde(rename = "clearGrantExpiry")]
+    pub clear_grant_expiry: Option<bool>, // Makes an update request remove the admin's current expiry
+    #[serde(rename = "appliedAdminKey")]
+    pub applied_admin_key: Option<String>, // Admin document changed when the approved request was applied
+    #[serde(rename = "appliedAt")]
+    pub applied_at: Option<u64>,         // Milliseconds
 }
 
 // Admin request validation function
@@ -156,10 +161,17 @@ fn validate_request_status_consistency(request: &AdminRequest) -> std::result::R
 
 // Validate role-specific business rules
 fn validate_request_role_rules(request: &AdminRequest) -> std::result::Result<(), String> {
+    let role_permissions = get_role_permissions(&request.target_role)?
+        .ok_or_else(|| format!("Unknown target role: {}", request.target_role))?;
+    
+    if let Some(permission) = request.target_permissions.iter().flatten().find(|p| !role_permissions.contains(p)) {
+        return Err(format!("Permission '{}' is not granted by role {}", permission, request.target_role));
+    }
+    
     match request.request_type {
         AdminRequestType::Add => {
             // Adding Platform Admin requires stricter justification
-            if request.target_role == AdminRole::PlatformAdmin {
+            if request.target_role == PLATFORM_ADMIN_ROLE {
                 if request.justification.len() < 50 {
                     return Err("Platform Admin additions require detailed justification (minimum 50 characters)".into());
                 }
@@ -167,7 +179,7 @@ fn validate_request_role_rules(request: &AdminRequest) -> std::result::Result<()
         },
         AdminRequestType::Remove => {
             // Removing admins requires careful consideration
-            if request.target_role == AdminRole::PlatformAdmin {
+            if request.target_role == PLATFORM_ADMIN_ROLE {
                 if request.justification.len() < 100 {
                     return Err("Platform Admin removals require extensive justification (minimum 100 characters)".into());
                 }
@@ -186,60 +198,304 @@ fn validate_request_role_rules(request: &AdminRequest) -> std::result::Result<()
 
 // Business rules validation for admin requests
 fn validate_admin_request_business_rules(request: &AdminRequest, context: &AssertSetDocContext) -> std::result::Result<(), String> {
-    // 1. Permission validation
+    // 1. Status transition validation
+    validate_request_status_transition(request, context)?;
+    
+    // 2. Permission validation
     validate_request_permissions(request, context)?;
     
-    // 2. Rate limiting validation
-    validate_request_rate_limits(context)?;
+    // 3. Separation of duties between requester and reviewer
+    validate_request_separation_of_duties(request, context)?;
     
-    // 3. Duplicate validation
+    // 4. Duplicate validation
     validate_duplicate_requests(request)?;
     
-    // 4. Request expiry validation
+    // 5. Target admin and grant expiry validation (new requests)
+    if context.data.data.current.is_none() {
+        validate_request_target(request)?;
+        validate_request_grant_expiry(request)?;
+    }
+    
+    // 6. Request expiry validation
     validate_request_expiry(request)?;
     
+    // 7. Application record is written by the platform only
+    validate_request_application_record(request, context)?;
+    
+    Ok(())
+}
+
+// Statuses a request may move to from its current status. Approved, rejected and
+// expired are final.
+fn allowed_status_transitions(status: &AdminRequestStatus) -> &'static [AdminRequestStatus] {
+    match status {
+        AdminRequestStatus::Pending => &[
+            AdminRequestStatus::Pending,
+            AdminRequestStatus::Approved,
+            AdminRequestStatus::Rejected,
+            AdminRequestStatus::Expired,
+        ],
+        AdminRequestStatus::Approved | AdminRequestStatus::Rejected | AdminRequestStatus::Expired => &[],
+    }
+}
+
+// Requests are created pending, edited only by their requester and reviewed once.
+// The satellite is exempt: it records when an approval was applied and turns approvals
+// it cannot apply into rejections.
+fn validate_request_status_transition(request: &AdminRequest, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    if is_satellite_caller(&context.caller) {
+        return Ok(());
+    }
+    
+    let Some(current_doc) = &context.data.data.current else {
+        if request.status != AdminRequestStatus::Pending {
+            return Err("New admin requests must be pending".into());
+        }
+        return Ok(());
+    };
+    
+    let current: AdminRequest = decode_doc_data(&current_doc.data)
+        .map_err(|e| format!("Cannot decode current admin request data: {}", e))?;
+    
+    if !allowed_status_transitions(&current.status).contains(&request.status) {
+        return Err(format!(
+            "Admin request status cannot change from {:?} to {:?}",
+            current.status, request.status
+        ));
+    }
+    
+    match request.status {
+        AdminRequestStatus::Pending => {
+            // Only the requester can edit their pending request
+            if current.requested_by != context.caller.to_text() {
+                return Err("Only the requester can edit a pending admin request".into());
+            }
+        },
+        AdminRequestStatus::Expired => {
+            let current_time = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+            match current.expires_at {
+                Some(expires_at) if expires_at <= current_time => {},
+                _ => return Err("Admin request has not reached its expiry date".into()),
+            }
+        },
+        AdminRequestStatus::Approved | AdminRequestStatus::Rejected => {},
+    }
+    
+    // Reviewing or expiring a request only records the outcome, the request itself is frozen
+    if request.status != AdminRequestStatus::Pending && !same_request_content(request, &current) {
+        return Err("Only the review fields can change when an admin request is reviewed or expired".into());
+    }
+    
+    Ok(())
+}
+
+// Whether two versions of a request ask for the same change
+fn same_request_content(a: &AdminRequest, b: &AdminRequest) -> bool {
+    a.id == b.id
+        && a.request_type == b.request_type
+        && a.target_admin_email == b.target_admin_email
+        && a.target_user_id == b.target_user_id
+        && a.target_role == b.target_role
+        && a.target_permissions == b.target_permissions
+        && a.justification == b.justification
+        && a.requested_by == b.requested_by
+        && a.created_at == b.created_at
+        && a.expires_at == b.expires_at
+        && a.grant_expires_at == b.grant_expires_at
+        && a.clear_grant_expiry == b.clear_grant_expiry
+}
+
+fn validate_request_application_record(request: &AdminRequest, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    if is_satellite_caller(&context.caller) {
+        return Ok(());
+    }
+    
+    let (applied_admin_key, applied_at) = match &context.data.data.current {
+        Some(current_doc) => {
+            let current: AdminRequest = decode_doc_data(&current_doc.data)
+                .map_err(|e| format!("Cannot decode current admin request data: {}", e))?;
+            (current.applied_admin_key, current.applied_at)
+        },
+        None => (None, None),
+    };
+    
+    if request.applied_admin_key != applied_admin_key || request.applied_at != applied_at {
+        return Err("appliedAdminKey and appliedAt are set by the platform when a request is applied".into());
+    }
+    
     Ok(())
 }
 
 // Validate requester permissions
-fn validate_request_permissions(_request: &AdminRequest, _context: &AssertSetDocContext) -> std::result::Result<(), String> {
-    // For testing, we'll allow all operations
-    // In production, you would check:
-    // - Only Compliance Officers can create admin requests
-    // - Only Platform Admins can approve/reject requests
-    
-    // let caller_permissions = get_caller_permissions(&context.caller).await?;
-    // 
-    // match request.status {
-    //     AdminRequestStatus::Pending => {
-    //         // Creating new requests - check admin_request_creation permission
-    //         if !caller_permissions.contains(&"admin_request_creation".to_string()) {
-    //             return Err("Only Compliance Officers can create admin requests".into());
-    //         }
-    //     },
-    //     AdminRequestStatus::Approved | AdminRequestStatus::Rejected => {
-    //         // Reviewing requests - check admin_request_approval permission
-    //         if !caller_permissions.contains(&"admin_request_approval".to_string()) {
-    //             return Err("Only Platform Admins can approve/reject admin requests".into());
-    //         }
-    //     },
-    //     _ => {}
-    // }
+fn validate_request_permissions(request: &AdminRequest, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    match request.status {
+        AdminRequestStatus::Pending => {
+            // Creating new requests - check admin_request_creation permission
+            require_permission(&context.caller, "admin_request_creation")
+                .map_err(|_| "Only authorized users can create admin requests (requires admin_request_creation permission)".to_string())
+        },
+        AdminRequestStatus::Approved | AdminRequestStatus::Rejected => {
+            // Reviewing requests - check admin_request_approval permission
+            require_permission(&context.caller, "admin_request_approval")
+                .map_err(|_| "Only authorized users can approve or reject admin requests (requires admin_request_approval permission)".to_string())?;
+            
+            // Approvers can only grant, change or remove roles within their own permissions
+            if request.status == AdminRequestStatus::Approved {
+                // The satellite applies the change on the approver's behalf, so the admin
+                // changes window is enforced on the approval itself
+                validate_operating_window(&context.caller, ADMIN_CHANGES, &context.data.key)?;
+                
+                validate_caller_can_manage_role(&context.caller, &request.target_role)?;
+                
+                if let Some((_, current)) = find_target_admin(request)? {
+                    validate_caller_can_manage_role(&context.caller, &current.role)?;
+                }
+            }
+            
+            Ok(())
+        },
+        AdminRequestStatus::Expired => {
+            // Expiring requests - the requester or an approver
+            if request.requested_by != context.caller.to_text() && !has_permission(&context.caller, "admin_request_approval")? {
+                return Err("Only the requester or an approver can expire an admin request (requires admin_request_approval permission)".into());
+            }
+            
+            Ok(())
+        },
+    }
+}
+
+// Requests are filed and reviewed under the caller's own identity, the requester can never
+// be the reviewer, and nobody reviews a request about their own admin account.
+// The satellite keeps the recorded identities when it writes on the reviewer's behalf.
+fn validate_request_separation_of_duties(request: &AdminRequest, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    if is_satellite_caller(&context.caller) {
+        return Ok(());
+    }
+    
+    let caller = context.caller.to_text();
+    
+    match &context.data.data.current {
+        None => {
+            if request.requested_by != caller {
+                return Err("Requested by must be the caller".into());
+            }
+        },
+        Some(current_doc) => {
+            let current: AdminRequest = decode_doc_data(&current_doc.data)
+                .map_err(|e| format!("Cannot decode current admin request data: {}", e))?;
+            if request.requested_by != current.requested_by {
+                return Err("Requested by cannot be changed".into());
+            }
+        },
+    }
+    
+    if !matches!(request.status, AdminRequestStatus::Approved | AdminRequestStatus::Rejected) {
+        return Ok(());
+    }
+    
+    if request.reviewed_by.as_deref() != Some(caller.as_str()) {
+        return Err("Reviewed by must be the caller".into());
+    }
+    
+    if request.requested_by == caller {
+        return Err("Admin requests must be reviewed by someone other than the requester".into());
+    }
+    
+    let target_key = match request.request_type {
+        AdminRequestType::Add => request.target_user_id.clone(),
+        AdminRequestType::Update | AdminRequestType::Remove => find_admin_key_by_email(&request.target_admin_email)?,
+    };
+    
+    if target_key.as_deref() == Some(caller.as_str()) {
+        return Err("Admins cannot review requests that target their own account".into());
+    }
     
     Ok(())
 }
 
-// Validate rate limits for request creation
-fn validate_request_rate_limits(_context: &AssertSetDocContext) -> std::result::Result<(), String> {
-    // For testing, we'll skip rate limiting
-    // In production, you would check:
-    // - Maximum requests per user per day/hour
-    // - System-wide request limits
-    
-    // let recent_requests = count_recent_requests_by_user(&context.caller, 86400).await?;
-    // if recent_requests >= 10 {
-    //     return Err("Too many admin requests created recently. Please wait before creating more.".into());
-    // }
+// Resolve the target admin through the email index: "add" must name a new email,
+// "update" and "remove" must name an existing admin
+fn validate_request_target(request: &AdminRequest) -> std::result::Result<(), String> {
+    let target = find_admin_key_by_email(&request.target_admin_email)?;
+    
+    match (&request.request_type, target) {
+        (AdminRequestType::Add, Some(_)) => Err(format!(
+            "Admin with email '{}' already exists", request.target_admin_email
+        )),
+        (AdminRequestType::Add, None) => {
+            let key = target_principal(request)?;
+            if get_doc_data::<AdminUser>(ADMINS_COLLECTION, &key)?.is_some() {
+                return Err(format!("Principal {} already has an admin account", key));
+            }
+            Ok(())
+        },
+        (AdminRequestType::Update | AdminRequestType::Remove, None) => Err(format!(
+            "No admin found with email '{}'", request.target_admin_email
+        )),
+        (AdminRequestType::Remove, Some(key)) => {
+            let admin: Option<AdminUser> = get_doc_data(ADMINS_COLLECTION, &key)?;
+            match admin {
+                Some(admin) if admin.role != request.target_role => Err(format!(
+                    "Target role must be the admin's current role ({})", admin.role
+                )),
+                _ => Ok(()),
+            }
+        },
+        _ => Ok(()),
+    }
+}
+
+// Principal text of the admin an "add" request creates
+fn target_principal(request: &AdminRequest) -> std::result::Result<String, String> {
+    let user_id = request.target_user_id.as_deref()
+        .ok_or("Add requests require the target user's principal (targetUserId)")?;
+    
+    match Principal::from_text(user_id) {
+        Ok(principal) if principal != Principal::anonymous() => Ok(principal.to_text()),
+        _ => Err(format!("targetUserId '{}' is not a valid principal", user_id)),
+    }
+}
+
+// Existing admin targeted by an "update" or "remove" request
+fn find_target_admin(request: &AdminRequest) -> std::result::Result<Option<(String, AdminUser)>, String> {
+    let Some(key) = find_admin_key_by_email(&request.target_admin_email)? else {
+        return Ok(None);
+    };
+    
+    let admin: Option<AdminUser> = get_doc_data(ADMINS_COLLECTION, &key)?;
+    
+    Ok(admin.map(|admin| (key, admin)))
+}
+
+// A time-bound grant must end in the future and only applies to add and update requests.
+// Update requests either set a new expiry, clear the current one, or keep it.
+fn validate_request_grant_expiry(request: &AdminRequest) -> std::result::Result<(), String> {
+    if request.clear_grant_expiry == Some(true) {
+        if request.request_type != AdminRequestType::Update {
+            return Err("Clearing the grant expiry only applies to update requests".into());
+        }
+        if request.grant_expires_at.is_some() {
+            return Err("A request cannot both set and clear the grant expiry".into());
+        }
+    }
+    
+    let Some(grant_expires_at) = request.grant_expires_at else {
+        return Ok(());
+    };
+    
+    if request.request_type == AdminRequestType::Remove {
+        return Err("Grant expiry only applies to add and update requests".into());
+    }
+    
+    if request.target_role == PLATFORM_ADMIN_ROLE {
+        return Err("Platform Admin grants cannot expire".into());
+    }
+    
+    let current_time = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    if grant_expires_at <= current_time {
+        return Err("Grant expiry must be in the future".into());
+    }
     
     Ok(())
 }
@@ -322,16 +578,13 @@ pub fn assert_admin_request_deletion(context: AssertDeleteDocContext) -> std::re
         return Err("Cannot delete approved admin requests (audit trail requirement)".into());
     }
     
-    // Allow deletion of pending, rejected, or expired requests
-    match request_to_delete.status {
-        AdminRequestStatus::Pending => {
-            // Only the requester should be able to delete their own pending requests
-            // In production, you would verify the caller matches requested_by
-        },
-        AdminRequestStatus::Rejected | AdminRequestStatus::Expired => {
-            // These can be cleaned up for housekeeping
-        },
-        _ => {}
+    // Only the requester can withdraw their own pending request;
+    // rejected and expired requests can be cleaned up for housekeeping
+    if request_to_delete.status == AdminRequestStatus::Pending
+        && !is_satellite_caller(&context.caller)
+        && request_to_delete.requested_by != context.caller.to_text()
+    {
+        return Err("Only the requester can delete a pending admin request".into());
     }
     
     // Log deletion attempt
@@ -357,7 +610,7 @@ pub fn handle_admin_request_changes(context: OnSetDocContext) -> std::result::Re
     
     // Enhanced logging for audit purposes
     ic_cdk::println!(
-        "Admin Request {}: {} - ID: {}, Type: {:?}, Status: {:?}, Target: {}, Role: {:?}", 
+        "Admin Request {}: {} - ID: {}, Type: {:?}, Status: {:?}, Target: {}, Role: {}", 
         operation_type,
         context.data.key,
         request_data.id,
@@ -367,18 +620,35 @@ pub fn handle_admin_request_changes(context: OnSetDocContext) -> std::result::Re
         request_data.target_role
     );
     
+    // Carry out requests that have just been approved
+    let previous_status = match &context.data.data.before {
+        Some(before_doc) => {
+            let previous: AdminRequest = decode_doc_data(&before_doc.data)
+                .map_err(|e| format!("Cannot decode previous admin request data: {}", e))?;
+            Some(previous.status)
+        },
+        None => None,
+    };
+    
+    if request_data.status == AdminRequestStatus::Approved
+        && previous_status != Some(AdminRequestStatus::Approved)
+        && request_data.applied_admin_key.is_none()
+    {
+        apply_approved_request(&context.data.key, &request_data)?;
+    }
+    
     // Log status-specific information
     match request_data.status {
         AdminRequestStatus::Approved => {
             ic_cdk::println!(
-                "CRITICAL: Admin request APPROVED - {:?} {:?} for {} by {:?}", 
+                "CRITICAL: Admin request APPROVED - {:?} {} for {} by {:?}", 
                 request_data.request_type, request_data.target_role,
                 request_data.target_admin_email, request_data.reviewed_by
             );
         },
         AdminRequestStatus::Rejected => {
             ic_cdk::println!(
-                "IMPORTANT: Admin request REJECTED - {:?} {:?} for {} by {:?}, Reason: {:?}", 
+                "IMPORTANT: Admin request REJECTED - {:?} {} for {} by {:?}, Reason: {:?}", 
                 request_data.request_type, request_data.target_role,
                 request_data.target_admin_email, request_data.reviewed_by,
                 request_data.review_notes
@@ -386,7 +656,7 @@ pub fn handle_admin_request_changes(context: OnSetDocContext) -> std::result::Re
         },
         AdminRequestStatus::Pending => {
             ic_cdk::println!(
-                "INFO: New admin request PENDING - {:?} {:?} for {} by {}", 
+                "INFO: New admin request PENDING - {:?} {} for {} by {}", 
                 request_data.request_type, request_data.target_role,
                 request_data.target_admin_email, request_data.requested_by
             );
@@ -397,8 +667,157 @@ pub fn handle_admin_request_changes(context: OnSetDocContext) -> std::result::Re
     // Additional processing for production:
     // - Send notification emails to approvers when new requests are created
     // - Send notification to requesters when requests are approved/rejected
-    // - Trigger automated admin addition/removal when approved
     // - Update audit trails and compliance records
     
     Ok(())
-}
\ No newline at end of file
+}
+
+// Apply an approved request, then link the changed admin document back to it.
+// If the change is rejected by the admin validation, the approval is turned into
+// a rejection that records why, so the request never claims a change that did not happen.
+fn apply_approved_request(key: &str, request: &AdminRequest) -> std::result::Result<(), String> {
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    
+    match carry_out_request(request, now) {
+        Ok(admin_key) => {
+            let applied = AdminRequest {
+                applied_admin_key: Some(admin_key.clone()),
+                applied_at: Some(now),
+                ..request.clone()
+            };
+            set_doc_data(ADMIN_REQUESTS_COLLECTION, key, &applied)?;
+            
+            ic_cdk::println!("CRITICAL: Admin request {} applied to admin {}", request.id, admin_key);
+            
+            record_activity_log(
+                "admin_request_applied",
+                "audit",
+                "info",
+                request.reviewed_by.clone(),
+                format!(
+                    "{:?} request {} applied: {} ({}) as {}",
+                    request.request_type, request.id, request.target_admin_email, admin_key, request.target_role
+                ),
+                Some("admin"),
+                Some(&admin_key),
+            )
+        },
+        Err(e) => {
+            let rejected = AdminRequest {
+                status: AdminRequestStatus::Rejected,
+                review_notes: Some(format!("Approval could not be applied and was rejected: {}", e)),
+                ..request.clone()
+            };
+            set_doc_data(ADMIN_REQUESTS_COLLECTION, key, &rejected)?;
+            
+            ic_cdk::println!("ERROR: Admin request {} could not be applied: {}", request.id, e);
+            
+            record_activity_log(
+                "admin_request_apply_failed",
+                "audit",
+                "error",
+                request.reviewed_by.clone(),
+                format!("{:?} request {} for {} rejected: {}", request.request_type, request.id, request.target_admin_email, e),
+                Some("admin_request"),
+                Some(key),
+            )
+        },
+    }
+}
+
+// Expiry of an admin after an update request: platform admin grants never expire, otherwise
+// the request sets a new expiry, clears it, or keeps the current one
+fn updated_grant_expiry(request: &AdminRequest, existing: &AdminUser) -> Option<u64> {
+    if request.target_role == PLATFORM_ADMIN_ROLE || request.clear_grant_expiry == Some(true) {
+        return None;
+    }
+    
+    request.grant_expires_at.or(existing.expires_at)
+}
+
+// Write the admin change an approved request asks for, as the satellite on behalf of the reviewer.
+// The write goes through the admin assertions. Returns the key of the changed admin document.
+fn carry_out_request(request: &AdminRequest, now: u64) -> std::result::Result<String, String> {
+    let reviewer = request.reviewed_by.clone()
+        .ok_or("Approved request has no reviewer")?;
+    
+    let permissions = match &request.target_permissions {
+        Some(permissions) => permissions.clone(),
+        None => get_role_permissions(&request.target_role)?
+            .ok_or_else(|| format!("Unknown target role: {}", request.target_role))?,
+    };
+    
+    match request.request_type {
+        AdminRequestType::Add => {
+            let key = target_principal(request)?;
+            if get_doc_data::<AdminUser>(ADMINS_COLLECTION, &key)?.is_some() {
+                return Err(format!("Principal {} already has an admin account", key));
+            }
+            
+            let admin = AdminUser {
+                email: request.target_admin_email.clone(),
+                role: request.target_role.clone(),
+                permissions,
+                created_by: reviewer.clone(),
+                active: true,
+                user_id: Some(key.clone()),
+                name: None,
+                created_at: Some(now),
+                last_active: None,
+                updated_at: Some(now),
+                updated_by: Some(reviewer),
+                deleted: Some(false),
+                deleted_at: None,
+                deleted_by: None,
+                restored_at: None,
+                restored_by: None,
+                expires_at: request.grant_expires_at,
+            };
+            
+            set_admin_data(&key, &admin)?;
+            
+            Ok(key)
+        },
+        AdminRequestType::Update => {
+            let (key, existing) = find_target_admin(request)?
+                .ok_or_else(|| format!("No admin found with email '{}'", request.target_admin_email))?;
+            if existing.is_deleted() {
+                return Err(format!("Admin {} has been deleted", request.target_admin_email));
+            }
+            
+            let admin = AdminUser {
+                role: request.target_role.clone(),
+                permissions,
+                expires_at: updated_grant_expiry(request, &existing),
+                updated_at: Some(now),
+                updated_by: Some(reviewer),
+                ..existing
+            };
+            
+            set_admin_data(&key, &admin)?;
+            
+            Ok(key)
+        },
+        AdminRequestType::Remove => {
+            let (key, existing) = find_target_admin(request)?
+                .ok_or_else(|| format!("No admin found with email '{}'", request.target_admin_email))?;
+            if existing.is_deleted() {
+                return Err(format!("Admin {} is already deleted", request.target_admin_email));
+            }
+            
+            let admin = AdminUser {
+                active: false,
+                deleted: Some(true),
+                deleted_at: Some(now),
+                deleted_by: Some(reviewer.clone()),
+                updated_at: Some(now),
+                updated_by: Some(reviewer),
+                ..existing
+            };
+            
+            set_admin_data(&key, &admin)?;
+            
+            Ok(key)
+        },
+    }
+}
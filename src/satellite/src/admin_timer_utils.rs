@@ -0,0 +1,143 @@
+use crate::activity_log_hooks::record_activity_log;
+use crate::admin_config_hooks::get_inactive_after_days;
+use crate::admin_hooks::{set_admin_data, AdminUser};
+use crate::authz::ADMINS_COLLECTION;
+use crate::store_utils::list_doc_data;
+use ic_cdk_timers::set_timer_interval;
+use std::time::Duration;
+
+// Periodic admin maintenance jobs. Timers do not survive upgrades,
+// so they are scheduled on init and again after every upgrade.
+
+const INACTIVITY_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const MS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+pub fn start_admin_timers() {
+    set_timer_interval(INACTIVITY_CHECK_INTERVAL, || {
+        if let Err(e) = deactivate_inactive_admins() {
+            ic_cdk::println!("ERROR: Inactive admin check failed: {}", e);
+        }
+    });
+
+    // Expired grants already carry no permissions; this keeps the documents in line
+    set_timer_interval(EXPIRY_CHECK_INTERVAL, || {
+        if let Err(e) = deactivate_expired_admins() {
+            ic_cdk::println!("ERROR: Expired admin check failed: {}", e);
+        }
+    });
+}
+
+// Deactivate active admins whose time-bound grant has ended.
+// Returns the number of admins deactivated.
+pub fn deactivate_expired_admins() -> std::result::Result<u32, String> {
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    let satellite = ic_cdk::api::canister_self().to_text();
+
+    let admins: Vec<(String, AdminUser)> = list_doc_data(ADMINS_COLLECTION)?;
+    let mut deactivated = 0;
+
+    for (key, admin) in admins {
+        if !admin.active || admin.is_deleted() || !admin.is_expired(now) {
+            continue;
+        }
+
+        let email = admin.email.clone();
+        let role = admin.role.clone();
+        let expires_at = admin.expires_at.unwrap_or_default();
+        let updated = AdminUser {
+            active: false,
+            updated_at: Some(now),
+            updated_by: Some(satellite.clone()),
+            ..admin
+        };
+
+        if let Err(e) = set_admin_data(&key, &updated) {
+            ic_cdk::println!("Expired admin {} ({}) kept active: {}", email, key, e);
+            continue;
+        }
+
+        record_activity_log(
+            "admin_grant_expired",
+            "admin",
+            "warning",
+            None,
+            format!("{} admin {} deactivated, grant expired at {}", role, email, expires_at),
+            Some("admin"),
+            Some(&key),
+        )?;
+
+        deactivated += 1;
+    }
+
+    if deactivated > 0 {
+        ic_cdk::println!("IMPORTANT: {} expired admin(s) deactivated", deactivated);
+    }
+
+    Ok(deactivated)
+}
+
+// Deactivate active admins whose last activity (or creation, if they never signed in)
+// is older than the configured threshold. The writes go through the admin assertions,
+// so the last active platform admin is kept. Returns the number of admins deactivated.
+pub fn deactivate_inactive_admins() -> std::result::Result<u32, String> {
+    let Some(days) = get_inactive_after_days()? else {
+        return Ok(0);
+    };
+
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    let cutoff = now.saturating_sub(days as u64 * MS_PER_DAY);
+    let satellite = ic_cdk::api::canister_self().to_text();
+
+    let admins: Vec<(String, AdminUser)> = list_doc_data(ADMINS_COLLECTION)?;
+    let mut deactivated = 0;
+
+    for (key, admin) in admins {
+        if !admin.active || admin.is_deleted() {
+            continue;
+        }
+
+        let Some(last_seen) = admin.last_active.or(admin.created_at) else {
+            continue;
+        };
+
+        if last_seen >= cutoff {
+            continue;
+        }
+
+        let email = admin.email.clone();
+        let role = admin.role.clone();
+        let updated = AdminUser {
+            active: false,
+            updated_at: Some(now),
+            updated_by: Some(satellite.clone()),
+            ..admin
+        };
+
+        if let Err(e) = set_admin_data(&key, &updated) {
+            ic_cdk::println!("Inactive admin {} ({}) kept active: {}", email, key, e);
+            continue;
+        }
+
+        record_activity_log(
+            "admin_deactivated_inactive",
+            "admin",
+            "warning",
+            None,
+            format!(
+                "{} admin {} deactivated after {} days without activity (last active {}). A reactivation request is required.",
+                role, email, days, last_seen
+            ),
+            Some("admin"),
+            Some(&key),
+        )?;
+
+        deactivated += 1;
+    }
+
+    if deactivated > 0 {
+        ic_cdk::println!("IMPORTANT: {} inactive admin(s) deactivated", deactivated);
+    }
+
+    Ok(deactivated)
+}
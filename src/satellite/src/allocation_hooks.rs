@@ -1,5 +1,7 @@
+use crate::operating_window_utils::{is_high_value_allocation, validate_operating_window, HIGH_VALUE_ALLOCATIONS};
+use crate::cause_hooks::adjust_cause_funds;
 use crate::waqf_types::AllocationData;
-use junobuild_satellite::{OnSetDocContext, AssertSetDocContext, AssertDeleteDocContext};
+use junobuild_satellite::{OnSetDocContext, OnDeleteDocContext, AssertSetDocContext, AssertDeleteDocContext};
 use junobuild_utils::decode_doc_data;
 
 // Validation constants
@@ -16,6 +18,11 @@ pub fn assert_allocation_operations(context: AssertSetDocContext) -> std::result
     // Validate allocation data
     validate_allocation_data(&allocation)?;
     
+    // High-value allocations are restricted to their operating window
+    if is_high_value_allocation(allocation.amount)? {
+        validate_operating_window(&context.caller, HIGH_VALUE_ALLOCATIONS, &context.data.key)?;
+    }
+    
     ic_cdk::println!(
         "Allocation validation passed: {} - Waqf: {}, Cause: {}, Amount: {}", 
         allocation.id, allocation.waqf_id, allocation.cause_id, allocation.amount
@@ -60,9 +67,44 @@ pub fn handle_allocation_changes(context: OnSetDocContext) -> std::result::Resul
         );
     }
     
+    // Keep the target cause's funds raised in sync
+    match &context.data.data.before {
+        Some(before_doc) => {
+            let previous: AllocationData = decode_doc_data(&before_doc.data)
+                .map_err(|e| format!("Cannot decode previous allocation data: {}", e))?;
+            
+            if previous.cause_id != allocation.cause_id {
+                adjust_cause_funds(&previous.cause_id, -previous.amount)?;
+                adjust_cause_funds(&allocation.cause_id, allocation.amount)?;
+            } else if (previous.amount - allocation.amount).abs() > f64::EPSILON {
+                adjust_cause_funds(&allocation.cause_id, allocation.amount - previous.amount)?;
+            }
+        },
+        None => {
+            adjust_cause_funds(&allocation.cause_id, allocation.amount)?;
+        }
+    }
+    
     Ok(())
 }
 
+// Reverse a deleted allocation from its cause's funds raised
+pub fn handle_allocation_deletion(context: OnDeleteDocContext) -> std::result::Result<(), String> {
+    let Some(deleted_doc) = &context.data.data else {
+        return Ok(());
+    };
+    
+    let allocation: AllocationData = decode_doc_data(&deleted_doc.data)
+        .map_err(|e| format!("Cannot decode deleted allocation data: {}", e))?;
+    
+    ic_cdk::println!(
+        "Allocation DELETE: {} - Waqf: {}, Cause: {}, Amount: {}",
+        allocation.id, allocation.waqf_id, allocation.cause_id, allocation.amount
+    );
+    
+    adjust_cause_funds(&allocation.cause_id, -allocation.amount)
+}
+
 fn validate_allocation_data(allocation: &AllocationData) -> std::result::Result<(), String> {
     // Validate ID
     if allocation.id.trim().is_empty() {
@@ -0,0 +1,92 @@
+use crate::admin_hooks::{set_admin_data, AdminUser};
+use crate::role_hooks::get_role_permissions;
+use crate::store_utils::{get_doc_data, is_satellite_caller};
+use candid::Principal;
+
+// Central authorization backed by the admins collection.
+// Everything here reads the datastore synchronously, so it can be called from any assert hook.
+
+pub const ADMINS_COLLECTION: &str = "admins";
+
+// lastActive is refreshed at most once per hour
+const ACTIVITY_RESOLUTION_MS: u64 = 60 * 60 * 1000;
+
+// Load the caller's admin document, if the caller is an active, non-deleted admin
+// whose grant has not expired
+pub fn get_caller_admin(caller: &Principal) -> std::result::Result<Option<AdminUser>, String> {
+    let admin: Option<AdminUser> = get_doc_data(ADMINS_COLLECTION, &caller.to_text())?;
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+
+    Ok(admin.filter(|admin| admin.active && !admin.is_deleted() && !admin.is_expired(now)))
+}
+
+// Effective permissions of an admin: a stored permission only counts if the admin's role
+// currently grants it. Deleted admins and admins holding an undefined role have no permissions.
+pub fn resolve_permissions(admin: &AdminUser) -> std::result::Result<Vec<String>, String> {
+    if admin.is_deleted() {
+        return Ok(Vec::new());
+    }
+
+    let allowed = get_role_permissions(&admin.role)?.unwrap_or_default();
+
+    Ok(admin.permissions.iter()
+        .filter(|permission| allowed.contains(permission))
+        .cloned()
+        .collect())
+}
+
+// Effective permissions of the caller. Non-admins have no permissions.
+pub fn get_caller_permissions(caller: &Principal) -> std::result::Result<Vec<String>, String> {
+    match get_caller_admin(caller)? {
+        Some(admin) => resolve_permissions(&admin),
+        None => Ok(Vec::new()),
+    }
+}
+
+// Whether the caller holds a permission. Writes issued by the satellite itself hold every permission.
+pub fn has_permission(caller: &Principal, permission: &str) -> std::result::Result<bool, String> {
+    if is_satellite_caller(caller) {
+        return Ok(true);
+    }
+
+    let granted = get_caller_permissions(caller)?.iter().any(|p| p == permission);
+
+    // A permission-checked call counts as admin activity
+    if granted {
+        if let Err(e) = record_admin_activity(caller) {
+            ic_cdk::println!("Admin activity of {} not recorded: {}", caller.to_text(), e);
+        }
+    }
+
+    Ok(granted)
+}
+
+// Refresh the caller's lastActive, which only the satellite writes.
+// Returns whether the admin document was updated.
+pub fn record_admin_activity(caller: &Principal) -> std::result::Result<bool, String> {
+    let Some(admin) = get_caller_admin(caller)? else {
+        return Ok(false);
+    };
+
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    if admin.last_active.is_some_and(|last_active| now.saturating_sub(last_active) < ACTIVITY_RESOLUTION_MS) {
+        return Ok(false);
+    }
+
+    let updated = AdminUser {
+        last_active: Some(now),
+        ..admin
+    };
+    set_admin_data(&caller.to_text(), &updated)?;
+
+    Ok(true)
+}
+
+// Fail unless the caller holds a permission
+pub fn require_permission(caller: &Principal, permission: &str) -> std::result::Result<(), String> {
+    if !has_permission(caller, permission)? {
+        return Err(format!("Caller is not authorized (requires {} permission)", permission));
+    }
+
+    Ok(())
+}
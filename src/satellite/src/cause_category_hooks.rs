@@ -0,0 +1,210 @@
+use crate::authz::has_permission;
+use crate::cause_hooks::Cause;
+use crate::store_utils::{get_doc_data, list_doc_data, set_doc_data};
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext};
+use junobuild_utils::decode_doc_data;
+
+pub const CAUSE_CATEGORIES_COLLECTION: &str = "cause_categories";
+const CAUSES_COLLECTION: &str = "causes";
+
+// Validation constants
+const MAX_SLUG_LENGTH: usize = 50;
+const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+const DEFAULT_LOCALE: &str = "en";
+
+// Categories available before the registry existed, used to seed a fresh collection.
+// (slug, English display name, zakat eligible)
+const DEFAULT_CATEGORIES: &[(&str, &str, bool)] = &[
+    ("education", "Education", true),
+    ("healthcare", "Healthcare", true),
+    ("poverty_alleviation", "Poverty Alleviation", true),
+    ("disaster_relief", "Disaster Relief", true),
+    ("environmental", "Environmental", false),
+    ("community_development", "Community Development", false),
+    ("orphan_care", "Orphan Care", true),
+    ("elder_care", "Elder Care", true),
+    ("humanitarian_aid", "Humanitarian Aid", true),
+    ("religious_services", "Religious Services", false),
+    ("other", "Other", false),
+];
+
+// Cause category managed by admins
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CauseCategory {
+    pub slug: String,                            // Also the document key, e.g. "water_sanitation"
+    #[serde(rename = "displayNames")]
+    pub display_names: HashMap<String, String>,  // Locale -> display name, "en" is required
+    #[serde(rename = "isActive")]
+    pub is_active: bool,
+    #[serde(rename = "zakatEligible")]
+    pub zakat_eligible: bool,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,
+}
+
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug.len() <= MAX_SLUG_LENGTH
+        && slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !slug.starts_with('_')
+        && !slug.ends_with('_')
+}
+
+// Cause category validation function
+fn validate_cause_category_data(category: &CauseCategory, key: &str) -> std::result::Result<(), String> {
+    // 1. Slug validation
+    if !is_valid_slug(&category.slug) {
+        return Err(format!(
+            "Invalid category slug '{}'. Use lowercase letters, digits and underscores (max {} characters)",
+            category.slug, MAX_SLUG_LENGTH
+        ));
+    }
+
+    if category.slug != key {
+        return Err("Category slug must match the document key".into());
+    }
+
+    // 2. Display names validation
+    match category.display_names.get(DEFAULT_LOCALE) {
+        Some(name) if !name.trim().is_empty() => {},
+        _ => return Err(format!("Category display name for locale '{}' is required", DEFAULT_LOCALE)),
+    }
+
+    for (locale, name) in &category.display_names {
+        if locale.trim().is_empty() || locale.len() > 10 {
+            return Err(format!("Invalid display name locale '{}'", locale));
+        }
+
+        if name.trim().is_empty() || name.len() > MAX_DISPLAY_NAME_LENGTH {
+            return Err(format!(
+                "Display name for locale '{}' must be between 1 and {} characters",
+                locale, MAX_DISPLAY_NAME_LENGTH
+            ));
+        }
+    }
+
+    // 3. Timestamp validation
+    if category.created_at == 0 || category.updated_at == 0 {
+        return Err("Category timestamps are required".into());
+    }
+
+    Ok(())
+}
+
+// Only cause managers can change the registry
+fn validate_category_permissions(caller: &Principal) -> std::result::Result<(), String> {
+    if !has_permission(caller, "cause_management")? {
+        return Err("Only authorized users can manage cause categories (requires cause_management permission)".into());
+    }
+
+    Ok(())
+}
+
+// Validate a category being assigned to a cause: it must be registered and active
+pub fn validate_category_reference(category: &str) -> std::result::Result<(), String> {
+    let slug = category.to_lowercase();
+
+    let registered: Option<CauseCategory> = get_doc_data(CAUSE_CATEGORIES_COLLECTION, &slug)?;
+    let registered = registered.ok_or_else(|| format!("Invalid category '{}'. Category is not registered", category))?;
+
+    if !registered.is_active {
+        return Err(format!("Category '{}' is no longer active", category));
+    }
+
+    Ok(())
+}
+
+// Main assertion function for cause category operations
+pub fn assert_cause_category_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
+    let category: CauseCategory = decode_doc_data(&context.data.data.proposed.data)
+        .map_err(|e| format!("Invalid cause category data structure: {}", e))?;
+
+    validate_cause_category_data(&category, &context.data.key)?;
+
+    validate_category_permissions(&context.caller)?;
+
+    ic_cdk::println!(
+        "Cause category validation passed: {} - Active: {}, Zakat eligible: {}",
+        category.slug, category.is_active, category.zakat_eligible
+    );
+
+    Ok(())
+}
+
+// Deletion assertion for cause categories
+pub fn assert_cause_category_deletion(context: AssertDeleteDocContext) -> std::result::Result<(), String> {
+    validate_category_permissions(&context.caller)?;
+
+    let slug = context.data.key.to_lowercase();
+
+    // A category still referenced by a cause cannot be removed, deactivate it instead
+    let causes: Vec<(String, Cause)> = list_doc_data(CAUSES_COLLECTION)?;
+    let referencing: Vec<String> = causes.into_iter()
+        .filter(|(_, cause)| cause.category.to_lowercase() == slug)
+        .map(|(key, _)| key)
+        .collect();
+
+    if !referencing.is_empty() {
+        return Err(format!(
+            "Cannot delete category '{}' - it is used by {} cause(s): {}. Deactivate it instead.",
+            slug, referencing.len(), referencing.join(", ")
+        ));
+    }
+
+    ic_cdk::println!("Cause category deletion: {}", slug);
+
+    Ok(())
+}
+
+// Handle cause category changes (logging)
+pub fn handle_cause_category_changes(context: OnSetDocContext) -> std::result::Result<(), String> {
+    let category: CauseCategory = decode_doc_data(&context.data.data.after.data)
+        .map_err(|e| format!("Cannot decode cause category data: {}", e))?;
+
+    let operation_type = if context.data.data.before.is_none() {
+        "CREATE"
+    } else {
+        "UPDATE"
+    };
+
+    ic_cdk::println!(
+        "Cause Category {}: {} - Active: {}, Zakat eligible: {}",
+        operation_type, category.slug, category.is_active, category.zakat_eligible
+    );
+
+    Ok(())
+}
+
+// Seed the registry with the legacy built-in categories, skipping any that already exist.
+// Returns the number of categories created.
+pub fn seed_default_categories() -> std::result::Result<u32, String> {
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    let mut created = 0;
+
+    for (slug, name, zakat_eligible) in DEFAULT_CATEGORIES {
+        if get_doc_data::<CauseCategory>(CAUSE_CATEGORIES_COLLECTION, slug)?.is_some() {
+            continue;
+        }
+
+        let category = CauseCategory {
+            slug: slug.to_string(),
+            display_names: HashMap::from([(DEFAULT_LOCALE.to_string(), name.to_string())]),
+            is_active: true,
+            zakat_eligible: *zakat_eligible,
+            created_at: now,
+            updated_at: now,
+        };
+
+        set_doc_data(CAUSE_CATEGORIES_COLLECTION, slug, &category)?;
+        created += 1;
+    }
+
+    ic_cdk::println!("Cause categories seeded: {} created", created);
+
+    Ok(created)
+}
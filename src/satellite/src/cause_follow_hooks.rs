@@ -0,0 +1,114 @@
+use crate::cause_hooks::{adjust_cause_followers, Cause};
+use crate::store_utils::{get_doc_data, is_satellite_caller};
+use serde::{Deserialize, Serialize};
+use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext, OnDeleteDocContext};
+use junobuild_utils::decode_doc_data;
+
+const CAUSES_COLLECTION: &str = "causes";
+
+// A principal following a cause. Documents are keyed "<causeId>:<follower>",
+// so each principal can follow a given cause only once.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CauseFollow {
+    #[serde(rename = "causeId")]
+    pub cause_id: String,
+    pub follower: String,                // Principal text of the follower
+    #[serde(rename = "followedAt")]
+    pub followed_at: u64,                // Milliseconds
+}
+
+// Document key for a (cause, follower) pair
+fn follow_key(cause_id: &str, follower: &str) -> String {
+    format!("{}:{}", cause_id, follower)
+}
+
+// Cause follow validation function
+fn validate_cause_follow_data(follow: &CauseFollow, key: &str) -> std::result::Result<(), String> {
+    if follow.cause_id.trim().is_empty() {
+        return Err("Cause ID is required".into());
+    }
+
+    if follow.follower.trim().is_empty() {
+        return Err("Follower is required".into());
+    }
+
+    if follow.followed_at == 0 {
+        return Err("Followed at timestamp is required".into());
+    }
+
+    if key != follow_key(&follow.cause_id, &follow.follower) {
+        return Err("Follow document key must be '<causeId>:<follower>'".into());
+    }
+
+    Ok(())
+}
+
+// Main assertion function for cause follow operations
+pub fn assert_cause_follow_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
+    let follow: CauseFollow = decode_doc_data(&context.data.data.proposed.data)
+        .map_err(|e| format!("Invalid cause follow data structure: {}", e))?;
+
+    validate_cause_follow_data(&follow, &context.data.key)?;
+
+    // Follows are immutable, unfollow by deleting the document
+    if context.data.data.current.is_some() {
+        return Err("Already following this cause".into());
+    }
+
+    // Users can only follow on their own behalf
+    if !is_satellite_caller(&context.caller) && follow.follower != context.caller.to_text() {
+        return Err("Follower must be the caller".into());
+    }
+
+    let cause: Option<Cause> = get_doc_data(CAUSES_COLLECTION, &follow.cause_id)?;
+    match cause {
+        Some(cause) if cause.status == "approved" && cause.is_active => {},
+        Some(_) => return Err("Only active, approved causes can be followed".into()),
+        None => return Err(format!("Cause {} does not exist", follow.cause_id)),
+    }
+
+    Ok(())
+}
+
+// Deletion assertion for cause follows
+pub fn assert_cause_follow_deletion(context: AssertDeleteDocContext) -> std::result::Result<(), String> {
+    let current_doc = context.data.data.current.as_ref()
+        .ok_or("No current document found for deletion")?;
+    let follow: CauseFollow = decode_doc_data(&current_doc.data)
+        .map_err(|e| format!("Cannot decode cause follow data for deletion: {}", e))?;
+
+    // Only the follower can unfollow
+    if !is_satellite_caller(&context.caller) && follow.follower != context.caller.to_text() {
+        return Err("Only the follower can unfollow a cause".into());
+    }
+
+    Ok(())
+}
+
+// New follow: increment the cause's followers
+pub fn handle_cause_follow_changes(context: OnSetDocContext) -> std::result::Result<(), String> {
+    if context.data.data.before.is_some() {
+        return Ok(());
+    }
+
+    let follow: CauseFollow = decode_doc_data(&context.data.data.after.data)
+        .map_err(|e| format!("Cannot decode cause follow data: {}", e))?;
+
+    ic_cdk::println!("Cause FOLLOW: {} by {}", follow.cause_id, follow.follower);
+
+    adjust_cause_followers(&follow.cause_id, 1)
+}
+
+// Unfollow: decrement the cause's followers
+pub fn handle_cause_follow_deletion(context: OnDeleteDocContext) -> std::result::Result<(), String> {
+    let Some(deleted_doc) = &context.data.data else {
+        return Ok(());
+    };
+
+    let follow: CauseFollow = decode_doc_data(&deleted_doc.data)
+        .map_err(|e| format!("Cannot decode deleted cause follow data: {}", e))?;
+
+    ic_cdk::println!("Cause UNFOLLOW: {} by {}", follow.cause_id, follow.follower);
+
+    adjust_cause_followers(&follow.cause_id, -1)
+}
@@ -1,12 +1,35 @@
+use crate::authz::{get_caller_permissions, has_permission};
+use crate::cause_category_hooks::validate_category_reference;
+use crate::cause_follow_hooks::CauseFollow;
+use crate::cause_version_hooks::{record_cause_version, set_cause_versioned};
+use crate::cause_image_hooks::{is_cause_image_path, validate_cause_image_exists};
+use crate::store_utils::{get_doc_data, is_satellite_caller, list_doc_data, set_doc_data};
+use crate::waqf_types::{AllocationData, WaqfData};
+use candid::{CandidType, Principal};
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext};
 use junobuild_utils::decode_doc_data;
 
-// Note: Frontend uses simple string values for status: "pending" | "approved" | "rejected"
+const CAUSES_COLLECTION: &str = "causes";
+const WAQFS_COLLECTION: &str = "waqfs";
+const ALLOCATIONS_COLLECTION: &str = "allocations";
+const CAUSE_FOLLOWS_COLLECTION: &str = "cause_follows";
+
+// Note: Frontend uses simple string values for status: "pending" | "approved" | "rejected" | "suspended"
 // No enums needed - validation is done via string matching
+const VALID_CAUSE_STATUSES: &[&str] = &["pending", "approved", "rejected", "suspended"];
+
+// Status transition matrix for causes
+const VALID_CAUSE_TRANSITIONS: &[(&str, &[&str])] = &[
+    ("pending", &["approved", "rejected"]),
+    ("rejected", &["pending"]),      // Resubmission after changes
+    ("approved", &["suspended"]),    // Approved causes never return to pending
+    ("suspended", &["approved"]),    // Reinstatement after review
+];
 
 // Cause structure matching frontend interface
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
 pub struct Cause {
     pub id: String,
     pub name: String,                    // Frontend uses "name" not "title"
@@ -17,7 +40,13 @@ pub struct Cause {
     pub category: String,
     #[serde(rename = "isActive")]
     pub is_active: bool,
-    pub status: String,                  // Frontend uses string: "pending" | "approved" | "rejected"
+    pub status: String,                  // Frontend uses string: "pending" | "approved" | "rejected" | "suspended"
+    #[serde(rename = "reviewedBy")]
+    pub reviewed_by: Option<String>,     // Reviewer principal, required once the cause leaves pending
+    #[serde(rename = "reviewedAt")]
+    pub reviewed_at: Option<String>,     // ISO timestamp of the last review
+    #[serde(rename = "reviewNotes")]
+    pub review_notes: Option<String>,    // Required for rejected and suspended causes
     #[serde(rename = "sortOrder")]
     pub sort_order: i32,                 // Frontend has sortOrder
     pub followers: i32,                  // Frontend tracks followers
@@ -68,6 +97,14 @@ fn validate_cause_data(cause: &Cause) -> std::result::Result<(), String> {
         return Err("Funds raised cannot be negative".into());
     }
     
+    if cause.followers < 0 {
+        return Err("Followers cannot be negative".into());
+    }
+    
+    if cause.sort_order < 0 {
+        return Err("Sort order cannot be negative".into());
+    }
+    
     if let Some(score) = cause.impact_score {
         if score < 0.0 || score > 100.0 {
             return Err("Impact score must be between 0 and 100".into());
@@ -77,9 +114,9 @@ fn validate_cause_data(cause: &Cause) -> std::result::Result<(), String> {
     // 4. Status validation
     validate_cause_status(&cause.status)?;
     
-    // 5. Cover image URL validation
+    // 5. Cover image validation (external URL or internal cause_images asset path)
     if let Some(ref url) = cause.cover_image {
-        if !url.is_empty() && !is_valid_image_url(url) {
+        if !url.is_empty() && !is_valid_image_url(url) && !is_cause_image_path(url) {
             return Err("Invalid cover image URL format".into());
         }
     }
@@ -98,19 +135,75 @@ fn validate_cause_data(cause: &Cause) -> std::result::Result<(), String> {
 
 // Validate cause status values
 fn validate_cause_status(status: &str) -> std::result::Result<(), String> {
-    let valid_statuses = ["pending", "approved", "rejected"];
-    
-    if !valid_statuses.contains(&status) {
+    if !VALID_CAUSE_STATUSES.contains(&status) {
         return Err(format!(
             "Invalid cause status '{}'. Valid statuses: {}",
             status,
-            valid_statuses.join(", ")
+            VALID_CAUSE_STATUSES.join(", ")
         ));
     }
     
     Ok(())
 }
 
+// Validate cause status transition
+fn validate_cause_status_transition(from: &str, to: &str) -> std::result::Result<(), String> {
+    if from == to {
+        return Ok(()); // No transition needed
+    }
+    
+    let allowed_transitions = VALID_CAUSE_TRANSITIONS
+        .iter()
+        .find(|(status, _)| *status == from)
+        .map(|(_, transitions)| *transitions)
+        .unwrap_or(&[]);
+    
+    if !allowed_transitions.contains(&to) {
+        return Err(format!("Invalid cause status transition from '{}' to '{}'", from, to));
+    }
+    
+    Ok(())
+}
+
+// Validate reviewer metadata when a cause leaves pending or changes status.
+// Writes issued by the satellite (funds, followers, listing order, retirement) keep
+// whatever metadata the cause has, so causes reviewed before it was recorded stay writable.
+fn validate_cause_review_metadata(
+    cause: &Cause,
+    previous: Option<&Cause>,
+    context: &AssertSetDocContext,
+) -> std::result::Result<(), String> {
+    if let Some(ref notes) = cause.review_notes {
+        if notes.len() > 2000 {
+            return Err("Review notes must be 2000 characters or less".into());
+        }
+    }
+    
+    let status_changed = previous.map(|p| p.status != cause.status).unwrap_or(true);
+    if cause.status == "pending" || !status_changed || is_satellite_caller(&context.caller) {
+        return Ok(());
+    }
+    
+    let reviewed_by = cause.reviewed_by.as_deref().unwrap_or("").trim();
+    let reviewed_at = cause.reviewed_at.as_deref().unwrap_or("").trim();
+    
+    if reviewed_by.is_empty() || reviewed_at.is_empty() {
+        return Err(format!("{} causes must have reviewer and review date", cause.status));
+    }
+    
+    if matches!(cause.status.as_str(), "rejected" | "suspended")
+        && cause.review_notes.as_deref().unwrap_or("").trim().is_empty() {
+        return Err(format!("Review notes are required for {} causes", cause.status));
+    }
+    
+    // A status change must be signed off by the caller making it
+    if reviewed_by != context.caller.to_text() {
+        return Err("Reviewer must be the principal performing the status change".into());
+    }
+    
+    Ok(())
+}
+
 // Validate cause business rules based on status
 fn validate_cause_status_rules(cause: &Cause) -> std::result::Result<(), String> {
     match cause.status.as_str() {
@@ -130,6 +223,12 @@ fn validate_cause_status_rules(cause: &Cause) -> std::result::Result<(), String>
                 return Err("Rejected causes cannot be active".into());
             }
         },
+        "suspended" => {
+            // Suspended causes are hidden until reinstated
+            if cause.is_active {
+                return Err("Suspended causes cannot be active".into());
+            }
+        },
         _ => {
             return Err(format!("Unknown cause status: {}", cause.status));
         }
@@ -159,87 +258,419 @@ fn is_valid_image_url(url: &str) -> bool {
 }
 
 // Business rules validation for causes
-fn validate_cause_business_rules(cause: &Cause, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+fn validate_cause_business_rules(cause: &Cause, previous: Option<&Cause>, context: &AssertSetDocContext) -> std::result::Result<(), String> {
     // 1. Permission validation
-    validate_cause_permissions(cause, context)?;
+    validate_cause_permissions(cause, previous, context)?;
+    
+    // 2. Category validation against the admin-managed registry, for new or changed categories.
+    //    Causes keep the category they already have (e.g. legacy or since deactivated), and the
+    //    satellite's own writes never change it.
+    let category_changed = previous
+        .map(|p| !p.category.eq_ignore_ascii_case(&cause.category))
+        .unwrap_or(true);
+    if category_changed && !is_satellite_caller(&context.caller) {
+        validate_category_reference(&cause.category)?;
+    }
     
-    // 2. Rate limiting validation
-    validate_cause_rate_limits(context)?;
+    // 3. Internal cover images must have been uploaded
+    if let Some(ref path) = cause.cover_image {
+        let changed = previous.map(|p| p.cover_image.as_ref() != Some(path)).unwrap_or(true);
+        if changed && is_cause_image_path(path) {
+            validate_cause_image_exists(path)?;
+        }
+    }
     
-    // 3. Category validation
-    validate_cause_category(&cause.category)?;
+    // 4. Unique sort order among listed causes
+    validate_cause_sort_order(cause, previous, context)?;
     
     Ok(())
 }
 
 // Validate cause permissions based on status
-fn validate_cause_permissions(cause: &Cause, _context: &AssertSetDocContext) -> std::result::Result<(), String> {
-    // For now, we'll allow all operations during testing
-    // In production, you would check caller permissions based on status changes
+fn validate_cause_permissions(cause: &Cause, previous: Option<&Cause>, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    // Writes issued by the satellite's own hooks are trusted
+    if is_satellite_caller(&context.caller) {
+        return Ok(());
+    }
+    
+    let caller_permissions = get_caller_permissions(&context.caller)?;
+    let holds = |permission: &str| caller_permissions.iter().any(|p| p == permission);
+    
+    let previous_status = previous.map(|p| p.status.as_str());
+    let status_changed = previous_status != Some(cause.status.as_str());
     
     match cause.status.as_str() {
-        "approved" => {
-            // Only users with cause_approval permission should be able to approve
-            // let caller_permissions = get_caller_permissions(&context.caller).await?;
-            // if !caller_permissions.contains(&"cause_approval".to_string()) {
-            //     return Err("Only authorized users can approve causes".into());
-            // }
-        },
-        "rejected" => {
-            // Only users with cause_approval permission should be able to reject
-            // Similar check as above
+        "approved" | "rejected" | "suspended" if status_changed => {
+            // Only users with cause_approval permission can approve, reject or suspend
+            if !holds("cause_approval") {
+                return Err(format!(
+                    "Only authorized users can mark causes as {} (requires cause_approval permission)",
+                    cause.status
+                ));
+            }
         },
         _ => {
-            // Other status changes might have different permission requirements
+            // Edits to an approved cause require cause_management permission
+            if previous_status == Some("approved") && !holds("cause_management") {
+                return Err("Only authorized users can edit approved causes (requires cause_management permission)".into());
+            }
         }
     }
     
+    // Non-admin submitters can only create pending causes
+    if previous.is_none() && cause.status != "pending" && caller_permissions.is_empty() {
+        return Err("New causes must be submitted with status 'pending'".into());
+    }
+    
     Ok(())
 }
 
-// Validate cause creation rate limits
-fn validate_cause_rate_limits(_context: &AssertSetDocContext) -> std::result::Result<(), String> {
-    // For testing, we'll skip rate limiting
-    // In production, you would check:
-    // - How many causes the user has created recently
-    // - Overall system limits
-    // - IP-based limits
-    
-    // let recent_causes = count_recent_causes_by_user(&context.caller, 3600).await?;
-    // if recent_causes >= 5 {
-    //     return Err("Too many causes created recently. Please wait before creating more.".into());
-    // }
+// Active approved causes make up the public listing
+fn is_listed(cause: &Cause) -> bool {
+    cause.status == "approved" && cause.is_active
+}
+
+// Listed causes must have a unique sort order. Checked when a listed cause's sort order changes.
+// A cause joining the listing is not checked: if its order is taken, the satellite moves it to
+// the next free order once it is written. The satellite's own writes (e.g. reordering) are trusted.
+fn validate_cause_sort_order(cause: &Cause, previous: Option<&Cause>, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    if is_satellite_caller(&context.caller) || !is_listed(cause) {
+        return Ok(());
+    }
+    
+    let unchanged = previous
+        .map(|p| !is_listed(p) || p.sort_order == cause.sort_order)
+        .unwrap_or(true);
+    if unchanged {
+        return Ok(());
+    }
+    
+    let listed: Vec<(String, Cause)> = list_doc_data::<Cause>(CAUSES_COLLECTION)?
+        .into_iter()
+        .filter(|(key, other)| key != &context.data.key && is_listed(other))
+        .collect();
+    
+    if let Some((_, conflict)) = listed.iter().find(|(_, other)| other.sort_order == cause.sort_order) {
+        let next_free = listed.iter().map(|(_, other)| other.sort_order).max().unwrap_or(0) + 1;
+        return Err(format!(
+            "Sort order {} is already used by cause '{}'. Use a free sort order (e.g. {}) or reorder the causes.",
+            cause.sort_order, conflict.name, next_free
+        ));
+    }
+    
+    Ok(())
+}
+
+// Give a cause that just joined the listing the next free sort order if its order is taken
+fn assign_free_sort_order(editor: &Principal, cause_id: &str, cause: &Cause) -> std::result::Result<(), String> {
+    let others: Vec<i32> = list_doc_data::<Cause>(CAUSES_COLLECTION)?
+        .into_iter()
+        .filter(|(key, other)| key != cause_id && is_listed(other))
+        .map(|(_, other)| other.sort_order)
+        .collect();
+    
+    if !others.contains(&cause.sort_order) {
+        return Ok(());
+    }
+    
+    let sort_order = others.iter().copied().max().unwrap_or(0) + 1;
+    let placed = Cause { sort_order, ..cause.clone() };
+    set_cause_versioned(editor, cause_id, &placed)?;
+    
+    ic_cdk::println!("Cause {} listed with sort order {} (was {}, already taken)", cause_id, sort_order, cause.sort_order);
     
     Ok(())
 }
 
-// Validate cause category
-fn validate_cause_category(category: &str) -> std::result::Result<(), String> {
-    let valid_categories = [
-        "education",
-        "healthcare", 
-        "poverty_alleviation",
-        "disaster_relief",
-        "environmental",
-        "community_development",
-        "orphan_care",
-        "elder_care",
-        "humanitarian_aid",
-        "religious_services",
-        "other"
-    ];
-    
-    if !valid_categories.contains(&category.to_lowercase().as_str()) {
+// Renumber the listed causes 1..n, keeping their current order (ties broken by creation date).
+// One-off repair path for duplicate sort orders written before they were enforced;
+// a failed write traps so no partial order is kept. Returns the number of causes moved.
+pub fn normalize_cause_sort_orders(editor: &Principal) -> std::result::Result<u32, String> {
+    let mut listed: Vec<(String, Cause)> = list_doc_data::<Cause>(CAUSES_COLLECTION)?
+        .into_iter()
+        .filter(|(_, cause)| is_listed(cause))
+        .collect();
+    listed.sort_by(|(a_key, a), (b_key, b)| {
+        (a.sort_order, &a.created_at, a_key).cmp(&(b.sort_order, &b.created_at, b_key))
+    });
+    
+    let mut updated = 0;
+    for (index, (key, mut cause)) in listed.into_iter().enumerate() {
+        let sort_order = index as i32 + 1;
+        if cause.sort_order == sort_order {
+            continue;
+        }
+        
+        cause.sort_order = sort_order;
+        if let Err(e) = set_cause_versioned(editor, &key, &cause) {
+            ic_cdk::trap(format!("Cause sort order repair failed on {}: {}", key, e));
+        }
+        updated += 1;
+    }
+    
+    ic_cdk::println!("Cause sort orders normalized by {} - {} cause(s) moved", editor.to_text(), updated);
+    
+    Ok(updated)
+}
+
+// Reorder the listed causes from an ordered list of cause IDs, assigning sort orders 1..n.
+// The list must contain every active approved cause exactly once. Everything is validated
+// before writing, and a failed write traps so no partial order is kept.
+// Requires cause_management permission. Returns the number of causes whose order changed.
+pub fn reorder_causes(caller: &Principal, ordered_ids: &[String]) -> std::result::Result<u32, String> {
+    if !has_permission(caller, "cause_management")? {
+        return Err("Only authorized users can reorder causes (requires cause_management permission)".into());
+    }
+    
+    let mut listed: HashMap<String, Cause> = list_doc_data::<Cause>(CAUSES_COLLECTION)?
+        .into_iter()
+        .filter(|(_, cause)| is_listed(cause))
+        .collect();
+    
+    let mut seen: Vec<&String> = Vec::with_capacity(ordered_ids.len());
+    for id in ordered_ids {
+        if seen.contains(&id) {
+            return Err(format!("Cause {} appears more than once", id));
+        }
+        if !listed.contains_key(id) {
+            return Err(format!("Cause {} is not an active approved cause", id));
+        }
+        seen.push(id);
+    }
+    
+    if ordered_ids.len() != listed.len() {
+        let missing: Vec<&String> = listed.keys().filter(|key| !ordered_ids.contains(key)).collect();
         return Err(format!(
-            "Invalid category '{}'. Valid categories: {}", 
-            category,
-            valid_categories.join(", ")
+            "Ordered list must include every active approved cause. Missing: {}",
+            missing.iter().map(|key| key.as_str()).collect::<Vec<_>>().join(", ")
         ));
     }
     
+    let mut updated = 0;
+    for (index, id) in ordered_ids.iter().enumerate() {
+        let Some(mut cause) = listed.remove(id) else {
+            continue;
+        };
+        
+        let sort_order = index as i32 + 1;
+        if cause.sort_order == sort_order {
+            continue;
+        }
+        
+        cause.sort_order = sort_order;
+        if let Err(e) = set_cause_versioned(caller, id, &cause) {
+            ic_cdk::trap(format!("Cause reorder failed on {}: {}", id, e));
+        }
+        updated += 1;
+    }
+    
+    ic_cdk::println!("Causes REORDERED by {} - {} cause(s) moved", caller.to_text(), updated);
+    
+    Ok(updated)
+}
+
+// Validate fields maintained by the satellite rather than by clients
+fn validate_server_maintained_fields(
+    cause: &Cause,
+    previous: Option<&Cause>,
+    context: &AssertSetDocContext,
+) -> std::result::Result<(), String> {
+    if is_satellite_caller(&context.caller) {
+        return Ok(());
+    }
+    
+    // Funds raised is derived from allocations
+    let previous_funds = previous.map(|p| p.funds_raised).unwrap_or(0.0);
+    if (cause.funds_raised - previous_funds).abs() > f64::EPSILON {
+        return Err("Funds raised is maintained from allocations and cannot be changed directly".into());
+    }
+    
+    // Followers is derived from cause_follows documents
+    let previous_followers = previous.map(|p| p.followers).unwrap_or(0);
+    if cause.followers != previous_followers {
+        return Err("Followers is maintained from cause follows and cannot be changed directly".into());
+    }
+    
+    Ok(())
+}
+
+// Write a change to the counters maintained by the satellite (funds raised, followers).
+// Counter changes are versioned like editorial ones, recorded as made by the satellite
+// so followers are not exposed in the public history.
+fn set_cause_counters(cause_id: &str, cause: &Cause) -> std::result::Result<(), String> {
+    set_cause_versioned(&ic_cdk::api::canister_self(), cause_id, cause)
+}
+
+// Round a monetary amount to cents to avoid floating point drift
+fn round_amount(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+// Add (or, with a negative delta, subtract) an allocation amount to a cause's funds raised
+pub fn adjust_cause_funds(cause_id: &str, delta: f64) -> std::result::Result<(), String> {
+    let cause: Option<Cause> = get_doc_data(CAUSES_COLLECTION, cause_id)?;
+    
+    let Some(mut cause) = cause else {
+        ic_cdk::println!("WARNING: Allocation references unknown cause {} - funds not updated", cause_id);
+        return Ok(());
+    };
+    
+    cause.funds_raised = round_amount((cause.funds_raised + delta).max(0.0));
+    set_cause_counters(cause_id, &cause)?;
+    
+    ic_cdk::println!("Cause funds updated: {} - Delta: {}, Raised: {}", cause_id, delta, cause.funds_raised);
+    
+    Ok(())
+}
+
+// Add or remove a follower from a cause's follower count
+pub fn adjust_cause_followers(cause_id: &str, delta: i32) -> std::result::Result<(), String> {
+    let cause: Option<Cause> = get_doc_data(CAUSES_COLLECTION, cause_id)?;
+    
+    let Some(mut cause) = cause else {
+        ic_cdk::println!("WARNING: Follow references unknown cause {} - followers not updated", cause_id);
+        return Ok(());
+    };
+    
+    cause.followers = (cause.followers + delta).max(0);
+    set_cause_counters(cause_id, &cause)?;
+    
     Ok(())
 }
 
+// Recompute funds raised for every cause from the allocations collection.
+// One-off repair path for documents written before funds were server-maintained.
+// Returns the number of causes whose total changed.
+pub fn recompute_cause_funds() -> std::result::Result<u32, String> {
+    let allocations: Vec<(String, AllocationData)> = list_doc_data(ALLOCATIONS_COLLECTION)?;
+    
+    let mut totals: HashMap<String, f64> = HashMap::new();
+    for (_, allocation) in allocations {
+        *totals.entry(allocation.cause_id).or_insert(0.0) += allocation.amount;
+    }
+    
+    let causes: Vec<(String, Cause)> = list_doc_data(CAUSES_COLLECTION)?;
+    let mut updated = 0;
+    
+    for (key, mut cause) in causes {
+        let total = round_amount(totals.get(&key).copied().unwrap_or(0.0));
+        if (cause.funds_raised - total).abs() > f64::EPSILON {
+            ic_cdk::println!("Cause funds recomputed: {} - {} -> {}", key, cause.funds_raised, total);
+            cause.funds_raised = total;
+            set_cause_counters(&key, &cause)?;
+            updated += 1;
+        }
+    }
+    
+    Ok(updated)
+}
+
+// Recompute every cause's follower count from the cause_follows collection.
+// Repair path for counts that drifted from the follow documents.
+// Returns the number of causes whose count changed.
+pub fn recompute_cause_followers() -> std::result::Result<u32, String> {
+    let follows: Vec<(String, CauseFollow)> = list_doc_data(CAUSE_FOLLOWS_COLLECTION)?;
+    
+    let mut counts: HashMap<String, i32> = HashMap::new();
+    for (_, follow) in follows {
+        *counts.entry(follow.cause_id).or_insert(0) += 1;
+    }
+    
+    let causes: Vec<(String, Cause)> = list_doc_data(CAUSES_COLLECTION)?;
+    let mut updated = 0;
+    
+    for (key, mut cause) in causes {
+        let count = counts.get(&key).copied().unwrap_or(0);
+        if cause.followers != count {
+            ic_cdk::println!("Cause followers recomputed: {} - {} -> {}", key, cause.followers, count);
+            cause.followers = count;
+            set_cause_counters(&key, &cause)?;
+            updated += 1;
+        }
+    }
+    
+    Ok(updated)
+}
+
+// Waqfs selecting a cause and allocation keys pointing at it
+struct CauseReferences {
+    waqfs: Vec<(String, WaqfData)>,
+    allocations: Vec<String>,
+}
+
+// Find the waqfs selecting a cause and the allocations made to it
+fn find_cause_references(cause_id: &str) -> std::result::Result<CauseReferences, String> {
+    let waqfs: Vec<(String, WaqfData)> = list_doc_data::<WaqfData>(WAQFS_COLLECTION)?
+        .into_iter()
+        .filter(|(_, waqf)| waqf.selected_causes.iter().any(|id| id == cause_id))
+        .collect();
+    
+    let allocations: Vec<String> = list_doc_data::<AllocationData>(ALLOCATIONS_COLLECTION)?
+        .into_iter()
+        .filter(|(_, allocation)| allocation.cause_id == cause_id)
+        .map(|(key, _)| key)
+        .collect();
+    
+    Ok(CauseReferences { waqfs, allocations })
+}
+
+// Retire a cause: move every waqf selecting it over to a replacement cause and deactivate it.
+// Allocations keep pointing at the retired cause as historical records.
+// Requires cause_management permission. Returns the number of waqfs migrated.
+pub fn retire_cause(caller: &Principal, cause_id: &str, replacement_id: &str) -> std::result::Result<u32, String> {
+    if !has_permission(caller, "cause_management")? {
+        return Err("Only authorized users can retire causes (requires cause_management permission)".into());
+    }
+    
+    if cause_id == replacement_id {
+        return Err("Replacement cause must be different from the retired cause".into());
+    }
+    
+    let mut cause: Cause = get_doc_data(CAUSES_COLLECTION, cause_id)?
+        .ok_or_else(|| format!("Cause {} does not exist", cause_id))?;
+    
+    let replacement: Cause = get_doc_data(CAUSES_COLLECTION, replacement_id)?
+        .ok_or_else(|| format!("Replacement cause {} does not exist", replacement_id))?;
+    
+    if replacement.status != "approved" || !replacement.is_active {
+        return Err("Replacement cause must be active and approved".into());
+    }
+    
+    let waqfs = find_cause_references(cause_id)?.waqfs;
+    let mut migrated = 0;
+    
+    for (key, mut waqf) in waqfs {
+        let mut selected_causes: Vec<String> = Vec::with_capacity(waqf.selected_causes.len());
+        for id in waqf.selected_causes {
+            let id = if id == cause_id { replacement_id.to_string() } else { id };
+            if !selected_causes.contains(&id) {
+                selected_causes.push(id);
+            }
+        }
+        waqf.selected_causes = selected_causes;
+        
+        // Trap rather than return, so a failed write rolls back the waqfs already migrated
+        if let Err(e) = set_doc_data(WAQFS_COLLECTION, &key, &waqf) {
+            ic_cdk::trap(format!("Cause retirement failed on waqf {}: {}", key, e));
+        }
+        migrated += 1;
+    }
+    
+    if cause.is_active {
+        cause.is_active = false;
+        if let Err(e) = set_cause_versioned(caller, cause_id, &cause) {
+            ic_cdk::trap(format!("Cause retirement failed on {}: {}", cause_id, e));
+        }
+    }
+    
+    ic_cdk::println!(
+        "Cause RETIRED: {} -> {} by {} - {} waqf(s) migrated",
+        cause_id, replacement_id, caller.to_text(), migrated
+    );
+    
+    Ok(migrated)
+}
+
 // Main assertion function for cause operations
 pub fn assert_cause_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
     // Decode cause data with proper error handling
@@ -252,8 +683,25 @@ pub fn assert_cause_operations(context: AssertSetDocContext) -> std::result::Res
     // Validate cause status business rules
     validate_cause_status_rules(&cause)?;
     
+    // Decode the current version for update checks
+    let previous: Option<Cause> = match &context.data.data.current {
+        Some(current_doc) => Some(decode_doc_data(&current_doc.data)
+            .map_err(|e| format!("Cannot decode current cause data: {}", e))?),
+        None => None,
+    };
+    
+    // Validate status transition and reviewer metadata
+    if let Some(ref previous) = previous {
+        validate_cause_status_transition(&previous.status, &cause.status)?;
+    }
+    
+    validate_cause_review_metadata(&cause, previous.as_ref(), &context)?;
+    
+    // Reject client changes to server-maintained fields
+    validate_server_maintained_fields(&cause, previous.as_ref(), &context)?;
+    
     // Business logic validation
-    validate_cause_business_rules(&cause, &context)?;
+    validate_cause_business_rules(&cause, previous.as_ref(), &context)?;
     
     // Log the validation attempt
     ic_cdk::println!("Cause validation passed: {} - Status: {}, Active: {}", 
@@ -279,6 +727,21 @@ pub fn assert_cause_deletion(context: AssertDeleteDocContext) -> std::result::Re
         return Err("Cannot delete causes that have received donations.".into());
     }
     
+    // Prevent dangling references from waqfs and allocations
+    let CauseReferences { waqfs, allocations } = find_cause_references(&context.data.key)?;
+    if !waqfs.is_empty() || !allocations.is_empty() {
+        let waqf_list: Vec<String> = waqfs.iter()
+            .map(|(key, waqf)| format!("{} ({})", waqf.name, key))
+            .collect();
+        return Err(format!(
+            "Cannot delete cause '{}' - it is referenced by {} waqf(s){} and {} allocation(s). Retire it into a replacement cause instead.",
+            cause_to_delete.name,
+            waqfs.len(),
+            if waqf_list.is_empty() { String::new() } else { format!(": {}", waqf_list.join(", ")) },
+            allocations.len()
+        ));
+    }
+    
     // Log critical deletion attempt
     ic_cdk::println!("Cause deletion attempt: {} - Status: {}, Raised: {}", 
                     cause_to_delete.name, cause_to_delete.status, cause_to_delete.funds_raised);
@@ -308,6 +771,22 @@ pub fn handle_cause_changes(context: OnSetDocContext) -> std::result::Result<(),
         cause_data.funds_raised
     );
     
+    // Append a snapshot to the version history
+    record_cause_version(&context)?;
+    
+    // A cause joining the listing gets a free sort order
+    let was_listed = match &context.data.data.before {
+        Some(before_doc) => {
+            let previous: Cause = decode_doc_data(&before_doc.data)
+                .map_err(|e| format!("Cannot decode previous cause data: {}", e))?;
+            is_listed(&previous)
+        },
+        None => false,
+    };
+    if is_listed(&cause_data) && !was_listed {
+        assign_free_sort_order(&context.caller, &context.data.key, &cause_data)?;
+    }
+    
     // Log status-specific information
     match cause_data.status.as_str() {
         "approved" => {
@@ -315,8 +794,12 @@ pub fn handle_cause_changes(context: OnSetDocContext) -> std::result::Result<(),
                            cause_data.name, cause_data.followers, cause_data.funds_raised);
         },
         "rejected" => {
-            ic_cdk::println!("NOTICE: Cause rejected - '{}'", 
-                           cause_data.name);
+            ic_cdk::println!("NOTICE: Cause rejected - '{}' by {:?}, Reason: {:?}", 
+                           cause_data.name, cause_data.reviewed_by, cause_data.review_notes);
+        },
+        "suspended" => {
+            ic_cdk::println!("NOTICE: Cause suspended - '{}' by {:?}, Reason: {:?}", 
+                           cause_data.name, cause_data.reviewed_by, cause_data.review_notes);
         },
         _ => {}
     }
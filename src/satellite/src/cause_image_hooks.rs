@@ -0,0 +1,177 @@
+use junobuild_satellite::{get_asset_store, id, AssertUploadAssetContext};
+use junobuild_storage::http::types::HeaderField;
+use junobuild_storage::runtime::get_chunk;
+use junobuild_storage::types::store::Chunk;
+
+pub const CAUSE_IMAGES_COLLECTION: &str = "cause_images";
+
+// Upload constraints for cause images
+const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024; // 10MB, matches the collection setup
+const MIN_IMAGE_DIMENSION: u32 = 200;
+const MAX_IMAGE_DIMENSION: u32 = 8000;
+const ALLOWED_CONTENT_TYPES: &[&str] = &["image/png", "image/jpeg", "image/webp", "image/gif"];
+
+// Internal asset paths look like "/cause_images/<path>"
+pub fn is_cause_image_path(path: &str) -> bool {
+    path.len() <= 2048
+        && path.starts_with(&format!("/{}/", CAUSE_IMAGES_COLLECTION))
+        && path.len() > CAUSE_IMAGES_COLLECTION.len() + 2
+        && !path.contains("..")
+}
+
+// Check that an internal cover image path points to an uploaded asset
+pub fn validate_cause_image_exists(path: &str) -> std::result::Result<(), String> {
+    let asset = get_asset_store(id(), &CAUSE_IMAGES_COLLECTION.to_string(), path.to_string())?;
+
+    if asset.is_none() {
+        return Err(format!("Cover image '{}' does not exist in {}", path, CAUSE_IMAGES_COLLECTION));
+    }
+
+    Ok(())
+}
+
+fn find_header<'a>(headers: &'a [HeaderField], name: &str) -> Option<&'a str> {
+    headers.iter()
+        .find(|header| header.0.eq_ignore_ascii_case(name))
+        .map(|header| header.1.as_str())
+}
+
+// Read the (width, height) of an image from the start of its content
+fn read_image_dimensions(content_type: &str, bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| bytes.get(i..i + 2).map(|b| u16::from_be_bytes([b[0], b[1]]) as u32);
+    let le16 = |i: usize| bytes.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as u32);
+    let le24 = |i: usize| bytes.get(i..i + 3).map(|b| b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16);
+
+    match content_type {
+        "image/png" => {
+            // Signature, then the IHDR chunk carries width and height as big-endian u32
+            if !bytes.starts_with(b"\x89PNG\r\n\x1a\n") || bytes.get(12..16)? != b"IHDR" {
+                return None;
+            }
+            let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+            let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+            Some((width, height))
+        },
+        "image/gif" => {
+            if !bytes.starts_with(b"GIF87a") && !bytes.starts_with(b"GIF89a") {
+                return None;
+            }
+            Some((le16(6)?, le16(8)?))
+        },
+        "image/webp" => {
+            if !bytes.starts_with(b"RIFF") || bytes.get(8..12)? != b"WEBP" {
+                return None;
+            }
+            match bytes.get(12..16)? {
+                b"VP8 " => Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff)),
+                b"VP8L" => {
+                    let b = bytes.get(21..25)?;
+                    let width = 1 + (((b[1] as u32 & 0x3f) << 8) | b[0] as u32);
+                    let height = 1 + (((b[3] as u32 & 0x0f) << 10) | (b[2] as u32) << 2 | (b[1] as u32 & 0xc0) >> 6);
+                    Some((width, height))
+                },
+                b"VP8X" => Some((1 + le24(24)?, 1 + le24(27)?)),
+                _ => None,
+            }
+        },
+        "image/jpeg" => {
+            if !bytes.starts_with(&[0xff, 0xd8]) {
+                return None;
+            }
+            // Walk the segments until a start-of-frame marker
+            let mut i = 2;
+            loop {
+                while *bytes.get(i)? != 0xff {
+                    i += 1;
+                }
+                while *bytes.get(i)? == 0xff {
+                    i += 1;
+                }
+                let marker = *bytes.get(i)?;
+                i += 1;
+
+                match marker {
+                    0xd8 | 0x01 | 0xd0..=0xd7 => continue,
+                    0xc0..=0xcf if marker != 0xc4 && marker != 0xc8 && marker != 0xcc => {
+                        return Some((be16(i + 5)?, be16(i + 3)?));
+                    },
+                    _ => i += be16(i)? as usize,
+                }
+            }
+        },
+        _ => None,
+    }
+}
+
+// Assertion for uploads to the cause images collection
+pub fn assert_cause_image_upload(context: AssertUploadAssetContext) -> std::result::Result<(), String> {
+    let batch = &context.data.batch;
+    let full_path = &batch.key.full_path;
+
+    // 1. Path validation
+    if !is_cause_image_path(full_path) {
+        return Err(format!("Cause images must be stored under /{}/", CAUSE_IMAGES_COLLECTION));
+    }
+
+    // 2. Content type validation
+    let content_type = find_header(&context.data.commit_batch.headers, "Content-Type")
+        .map(|value| value.split(';').next().unwrap_or("").trim().to_lowercase())
+        .ok_or("Cause images must be uploaded with a Content-Type header")?;
+
+    if !ALLOWED_CONTENT_TYPES.contains(&content_type.as_str()) {
+        return Err(format!(
+            "Invalid image type: {}. Allowed types: {}",
+            content_type, ALLOWED_CONTENT_TYPES.join(", ")
+        ));
+    }
+
+    // Compressed encodings would hide the image header
+    if let Some(encoding_type) = &batch.encoding_type {
+        if encoding_type != "identity" {
+            return Err(format!("Cause images must be uploaded without content encoding (got {})", encoding_type));
+        }
+    }
+
+    // 3. Size validation
+    let mut chunks: Vec<Chunk> = context.data.commit_batch.chunk_ids.iter()
+        .map(|chunk_id| get_chunk(chunk_id).ok_or("Uploaded image chunk not found"))
+        .collect::<std::result::Result<_, _>>()?;
+    chunks.sort_by_key(|chunk| chunk.order_id);
+
+    let size: usize = chunks.iter().map(|chunk| chunk.content.len()).sum();
+    if size == 0 {
+        return Err("Cause image is empty".into());
+    }
+
+    if size > MAX_IMAGE_SIZE {
+        return Err(format!(
+            "Cause image is too large ({} bytes). Maximum size is {} bytes",
+            size, MAX_IMAGE_SIZE
+        ));
+    }
+
+    // 4. Dimension validation, which also checks the content matches its declared type
+    let (width, height) = read_image_dimensions(&content_type, &chunks[0].content)
+        .ok_or_else(|| format!("Cannot read image dimensions, the file is not a valid {}", content_type))?;
+
+    if width < MIN_IMAGE_DIMENSION || height < MIN_IMAGE_DIMENSION {
+        return Err(format!(
+            "Cause image is too small ({}x{}). Minimum size is {}x{}",
+            width, height, MIN_IMAGE_DIMENSION, MIN_IMAGE_DIMENSION
+        ));
+    }
+
+    if width > MAX_IMAGE_DIMENSION || height > MAX_IMAGE_DIMENSION {
+        return Err(format!(
+            "Cause image is too large ({}x{}). Maximum size is {}x{}",
+            width, height, MAX_IMAGE_DIMENSION, MAX_IMAGE_DIMENSION
+        ));
+    }
+
+    ic_cdk::println!(
+        "Cause image upload validation passed: {} - {} {}x{}, {} bytes",
+        full_path, content_type, width, height, size
+    );
+
+    Ok(())
+}
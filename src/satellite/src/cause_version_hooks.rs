@@ -0,0 +1,172 @@
+use crate::authz::get_caller_permissions;
+use crate::cause_hooks::Cause;
+use crate::store_utils::{get_doc_data, is_satellite_caller, list_doc_data_with_prefix, set_doc_data, set_doc_data_upsert};
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext};
+use junobuild_utils::decode_doc_data;
+
+pub const CAUSE_VERSIONS_COLLECTION: &str = "cause_versions";
+const CAUSES_COLLECTION: &str = "causes";
+
+// A single changed field, values are JSON encoded (None when the field was absent)
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct CauseFieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+// Snapshot of a cause after a change. Documents are keyed "<causeId>:<version>",
+// with the version zero-padded so keys sort in version order.
+// Every change is recorded, including the satellite's updates to fundsRaised and followers,
+// so a snapshot's counters are the values the cause had at that version.
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct CauseVersion {
+    #[serde(rename = "causeId")]
+    pub cause_id: String,
+    pub version: u64,                    // Version of the cause document
+    pub changes: Vec<CauseFieldChange>,  // Empty for the initial version
+    #[serde(rename = "editedBy")]
+    pub edited_by: String,               // Principal text of the editor
+    #[serde(rename = "editedAt")]
+    pub edited_at: u64,                  // Milliseconds
+    pub snapshot: Cause,
+}
+
+fn version_key(cause_id: &str, version: u64) -> String {
+    format!("{}:{:010}", cause_id, version)
+}
+
+fn to_json_object(cause: &Cause) -> std::result::Result<serde_json::Map<String, Value>, String> {
+    match serde_json::to_value(cause).map_err(|e| format!("Cannot encode cause: {}", e))? {
+        Value::Object(map) => Ok(map),
+        _ => Err("Cause did not encode to an object".into()),
+    }
+}
+
+// Field-level diff between two versions of a cause
+fn diff_causes(before: &Cause, after: &Cause) -> std::result::Result<Vec<CauseFieldChange>, String> {
+    let before = to_json_object(before)?;
+    let after = to_json_object(after)?;
+
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    Ok(fields.into_iter()
+        .filter(|field| before.get(*field) != after.get(*field))
+        .map(|field| CauseFieldChange {
+            field: field.clone(),
+            before: before.get(field).map(|value| value.to_string()),
+            after: after.get(field).map(|value| value.to_string()),
+        })
+        .collect())
+}
+
+// Versions are only written by the satellite
+pub fn assert_cause_version_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
+    if !is_satellite_caller(&context.caller) {
+        return Err("Cause versions are recorded by the platform and cannot be written directly".into());
+    }
+
+    Ok(())
+}
+
+// Version history is append-only
+pub fn assert_cause_version_deletion(_context: AssertDeleteDocContext) -> std::result::Result<(), String> {
+    Err("Cause versions cannot be deleted".into())
+}
+
+// Append a snapshot of a cause after it was created or updated
+pub fn record_cause_version(context: &OnSetDocContext) -> std::result::Result<(), String> {
+    let after_doc = &context.data.data.after;
+    let cause: Cause = decode_doc_data(&after_doc.data)
+        .map_err(|e| format!("Cannot decode cause data: {}", e))?;
+
+    let changes = match &context.data.data.before {
+        Some(before_doc) => {
+            let previous: Cause = decode_doc_data(&before_doc.data)
+                .map_err(|e| format!("Cannot decode previous cause data: {}", e))?;
+            let changes = diff_causes(&previous, &cause)?;
+
+            if changes.is_empty() {
+                return Ok(());
+            }
+
+            changes
+        },
+        None => Vec::new(),
+    };
+
+    let version = after_doc.version.unwrap_or(1);
+    let snapshot = CauseVersion {
+        cause_id: context.data.key.clone(),
+        version,
+        changes,
+        edited_by: context.caller.to_text(),
+        edited_at: after_doc.updated_at / 1_000_000, // Convert to milliseconds
+        snapshot: cause,
+    };
+
+    set_doc_data(CAUSE_VERSIONS_COLLECTION, &version_key(&context.data.key, version), &snapshot)?;
+
+    ic_cdk::println!(
+        "Cause version recorded: {} v{} - {} field(s) changed by {}",
+        snapshot.cause_id, version, snapshot.changes.len(), snapshot.edited_by
+    );
+
+    Ok(())
+}
+
+// Write a cause from a satellite endpoint and record its version. Writes made by the
+// satellite skip on_set_doc, so they would otherwise be missing from the history.
+// The editor is the principal that called the endpoint.
+pub fn set_cause_versioned(editor: &Principal, cause_id: &str, cause: &Cause) -> std::result::Result<(), String> {
+    let data = set_doc_data_upsert(CAUSES_COLLECTION, cause_id, cause)?;
+
+    record_cause_version(&OnSetDocContext {
+        caller: *editor,
+        data,
+    })
+}
+
+// Non-admins can only read the history of approved causes
+fn validate_history_access(caller: &Principal, cause_id: &str) -> std::result::Result<(), String> {
+    if !get_caller_permissions(caller)?.is_empty() {
+        return Ok(());
+    }
+
+    let cause: Option<Cause> = get_doc_data(CAUSES_COLLECTION, cause_id)?;
+    match cause {
+        Some(cause) if cause.status == "approved" => Ok(()),
+        _ => Err(format!("History of cause {} is not available", cause_id)),
+    }
+}
+
+// Latest recorded snapshot matching a predicate
+fn find_cause_version<F>(caller: &Principal, cause_id: &str, predicate: F) -> std::result::Result<Option<CauseVersion>, String>
+where
+    F: Fn(&CauseVersion) -> bool,
+{
+    validate_history_access(caller, cause_id)?;
+
+    let versions: Vec<(String, CauseVersion)> =
+        list_doc_data_with_prefix(CAUSE_VERSIONS_COLLECTION, &format!("{}:", cause_id))?;
+
+    Ok(versions.into_iter()
+        .map(|(_, version)| version)
+        .filter(|version| version.cause_id == cause_id && predicate(version))
+        .max_by_key(|version| version.version))
+}
+
+// The cause as it stood at a given document version
+pub fn get_cause_version_at(caller: &Principal, cause_id: &str, version: u64) -> std::result::Result<Option<CauseVersion>, String> {
+    find_cause_version(caller, cause_id, |v| v.version <= version)
+}
+
+// The cause as it stood at a given time (milliseconds)
+pub fn get_cause_version_at_time(caller: &Principal, cause_id: &str, timestamp: u64) -> std::result::Result<Option<CauseVersion>, String> {
+    find_cause_version(caller, cause_id, |v| v.edited_at <= timestamp)
+}
@@ -1,3 +1,6 @@
+use crate::authz::has_permission;
+use crate::matching_campaign_hooks::apply_donation_matches;
+use crate::store_utils::{is_satellite_caller, list_doc_data_with_prefix};
 use crate::waqf_types::DonationData;
 use junobuild_satellite::{OnSetDocContext, AssertSetDocContext, AssertDeleteDocContext};
 use junobuild_utils::decode_doc_data;
@@ -7,6 +10,7 @@ const MIN_DONATION_AMOUNT: f64 = 0.01;
 const MAX_DONATION_AMOUNT: f64 = 1_000_000.0;
 const VALID_DONATION_STATUSES: &[&str] = &["completed", "pending", "failed"];
 const VALID_CURRENCIES: &[&str] = &["USD", "EUR", "GBP", "SAR", "AED"];
+const DONATIONS_COLLECTION: &str = "donations";
 
 pub fn assert_donation_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
     // Decode donation data
@@ -16,6 +20,27 @@ pub fn assert_donation_operations(context: AssertSetDocContext) -> std::result::
     // Validate donation data
     validate_donation_data(&donation)?;
     
+    // Matched-donation records are only created by the matching campaign hook
+    if (donation.matching_campaign_id.is_some() || donation.matched_donation_id.is_some())
+        && !is_satellite_caller(&context.caller) {
+        return Err("Matched donations can only be created by a matching campaign".into());
+    }
+    
+    // Completing a donation draws on sponsor matching pools, so only finance admins
+    // (or the satellite) confirm payments and change completed donations
+    let previous_status = match &context.data.data.current {
+        Some(current_doc) => {
+            let previous: DonationData = decode_doc_data(&current_doc.data)
+                .map_err(|e| format!("Cannot decode current donation data: {}", e))?;
+            Some(previous.status)
+        },
+        None => None,
+    };
+    let touches_completed = donation.status == "completed" || previous_status.as_deref() == Some("completed");
+    if touches_completed && !has_permission(&context.caller, "financial_oversight")? {
+        return Err("Only finance admins can confirm or change completed donations (requires financial_oversight permission)".into());
+    }
+    
     ic_cdk::println!(
         "Donation validation passed: {} - Amount: {} {}, Status: {}", 
         donation.id, donation.amount, donation.currency, donation.status
@@ -24,10 +49,27 @@ pub fn assert_donation_operations(context: AssertSetDocContext) -> std::result::
     Ok(())
 }
 
-pub fn assert_donation_deletion(_context: AssertDeleteDocContext) -> std::result::Result<(), String> {
-    // Generally allow donation deletion for admin purposes
-    // Could add business rules here if needed
-    ic_cdk::println!("Donation deletion requested");
+pub fn assert_donation_deletion(context: AssertDeleteDocContext) -> std::result::Result<(), String> {
+    if is_satellite_caller(&context.caller) {
+        return Ok(());
+    }
+    
+    let current_doc = context.data.data.current.as_ref()
+        .ok_or("No current document found for deletion")?;
+    let donation: DonationData = decode_doc_data(&current_doc.data)
+        .map_err(|e| format!("Cannot decode donation data for deletion: {}", e))?;
+    
+    // Matches and the donations they matched stay, as the campaign's matched total counts them
+    if donation.matching_campaign_id.is_some() {
+        return Err("Matched-donation records cannot be deleted".into());
+    }
+    
+    let matches: Vec<(String, DonationData)> = list_doc_data_with_prefix(DONATIONS_COLLECTION, "match_")?;
+    if matches.iter().any(|(_, m)| m.matched_donation_id.as_deref() == Some(context.data.key.as_str())) {
+        return Err("Cannot delete a donation that has been matched by a campaign".into());
+    }
+    
+    ic_cdk::println!("Donation deletion requested: {}", context.data.key);
     Ok(())
 }
 
@@ -51,6 +93,20 @@ pub fn handle_donation_changes(context: OnSetDocContext) -> std::result::Result<
         donation.status
     );
     
+    // Apply sponsor matching when a donation completes
+    let was_completed = match &context.data.data.before {
+        Some(before_doc) => {
+            let previous: DonationData = decode_doc_data(&before_doc.data)
+                .map_err(|e| format!("Cannot decode previous donation data: {}", e))?;
+            previous.status == "completed"
+        },
+        None => false,
+    };
+    
+    if donation.status == "completed" && !was_completed && donation.matching_campaign_id.is_none() {
+        apply_donation_matches(&donation)?;
+    }
+    
     Ok(())
 }
 
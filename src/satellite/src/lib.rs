@@ -1,36 +1,79 @@
 use junobuild_macros::{
-    assert_delete_doc, assert_set_doc, on_delete_asset,
+    assert_delete_doc, assert_set_doc, assert_upload_asset, on_delete_asset,
     on_delete_doc, on_delete_filtered_assets, on_delete_filtered_docs,
-    on_delete_many_assets, on_delete_many_docs, on_set_doc, on_set_many_docs, on_upload_asset
+    on_delete_many_assets, on_delete_many_docs, on_init, on_post_upgrade, on_set_doc, on_set_many_docs,
+    on_upload_asset
 };
 use junobuild_satellite::{
-    include_satellite, AssertDeleteDocContext, AssertSetDocContext, OnDeleteAssetContext,
+    include_satellite, AssertDeleteDocContext, AssertSetDocContext, AssertUploadAssetContext, OnDeleteAssetContext,
     OnDeleteDocContext, OnDeleteFilteredAssetsContext, OnDeleteFilteredDocsContext,
     OnDeleteManyAssetsContext, OnDeleteManyDocsContext, OnSetDocContext, OnSetManyDocsContext, OnUploadAssetContext
 };
 
 mod admin_hooks;
+mod admin_email_hooks;
+mod admin_config_hooks;
+mod admin_timer_utils;
+mod role_hooks;
 mod cause_hooks;
 mod admin_request_hooks;
 mod activity_log_hooks;
 mod donation_hooks;
 mod allocation_hooks;
+mod matching_campaign_hooks;
+mod cause_category_hooks;
+mod cause_follow_hooks;
+mod cause_image_hooks;
+mod cause_version_hooks;
 pub mod waqf_types;
 pub mod waqf_hooks;
 
 mod waqf_utils;
+mod store_utils;
+mod authz;
+mod rate_limit_utils;
+mod operating_window_utils;
 
 // Import all validation hooks
 use crate::admin_hooks::{
     assert_admin_operations,
     assert_admin_deletion,
     handle_admin_changes,
+    handle_admin_deletion,
+    seed_platform_admin,
+    migrate_admin_keys,
+};
+
+use crate::admin_config_hooks::{
+    assert_admin_config_operations,
+    assert_admin_config_deletion,
+    handle_admin_config_changes,
+};
+
+use crate::admin_timer_utils::start_admin_timers;
+
+use crate::role_hooks::{
+    assert_role_operations,
+    assert_role_deletion,
+    handle_role_changes,
+    seed_default_roles,
+};
+
+use crate::admin_email_hooks::{
+    assert_admin_email_operations,
+    assert_admin_email_deletion,
+    rebuild_email_index,
 };
 
 use crate::cause_hooks::{
     assert_cause_operations,
     assert_cause_deletion,
     handle_cause_changes,
+    recompute_cause_funds,
+    recompute_cause_followers,
+    normalize_cause_sort_orders,
+    reorder_causes,
+    retire_cause,
 };
 
 use crate::admin_request_hooks::{
@@ -61,8 +104,54 @@ use crate::allocation_hooks::{
     assert_allocation_operations,
     assert_allocation_deletion,
     handle_allocation_changes,
+    handle_allocation_deletion,
+};
+
+use crate::cause_category_hooks::{
+    assert_cause_category_operations,
+    assert_cause_category_deletion,
+    handle_cause_category_changes,
+    seed_default_categories,
+};
+
+use crate::cause_follow_hooks::{
+    assert_cause_follow_operations,
+    assert_cause_follow_deletion,
+    handle_cause_follow_changes,
+    handle_cause_follow_deletion,
+};
+
+use crate::cause_image_hooks::assert_cause_image_upload;
+
+use crate::cause_version_hooks::{
+    assert_cause_version_operations,
+    assert_cause_version_deletion,
+    get_cause_version_at,
+    get_cause_version_at_time,
+    CauseVersion,
+};
+
+use crate::authz::record_admin_activity;
+use crate::rate_limit_utils::consume_rate_limit;
+use crate::store_utils::is_admin_controller_caller;
+
+use crate::matching_campaign_hooks::{
+    assert_matching_campaign_operations,
+    assert_matching_campaign_deletion,
+    handle_matching_campaign_changes,
 };
 
+// Lifecycle hooks: schedule the periodic admin maintenance jobs
+#[on_init]
+fn on_init() {
+    start_admin_timers();
+}
+
+#[on_post_upgrade]
+fn on_post_upgrade() {
+    start_admin_timers();
+}
+
 // Main on_set_doc handler
 #[on_set_doc]
 fn on_set_doc(context: OnSetDocContext) -> std::result::Result<(), String> {
@@ -71,6 +160,12 @@ fn on_set_doc(context: OnSetDocContext) -> std::result::Result<(), String> {
         "admins" => {
             handle_admin_changes(context)?
         },
+        "admin_config" => {
+            handle_admin_config_changes(context)?
+        },
+        "roles" => {
+            handle_role_changes(context)?
+        },
         "causes" => {
             handle_cause_changes(context)?
         },
@@ -89,6 +184,15 @@ fn on_set_doc(context: OnSetDocContext) -> std::result::Result<(), String> {
         "allocations" => {
             handle_allocation_changes(context)?
         },
+        "matching_campaigns" => {
+            handle_matching_campaign_changes(context)?
+        },
+        "cause_categories" => {
+            handle_cause_category_changes(context)?
+        },
+        "cause_follows" => {
+            handle_cause_follow_changes(context)?
+        },
         _ => {
             // Log unknown collection access
             ic_cdk::println!("Document change in unhandled collection: {}", context.data.collection);
@@ -100,11 +204,25 @@ fn on_set_doc(context: OnSetDocContext) -> std::result::Result<(), String> {
 
 #[assert_set_doc]
 fn assert_set_doc(context: AssertSetDocContext) -> std::result::Result<(), String> {
+    // Rate limits apply to every collection. A write rejected further down traps and
+    // rolls back, so it does not use up a token.
+    let action = if context.data.data.current.is_none() { "create" } else { "update" };
+    consume_rate_limit(&context.caller, &context.data.collection, action)?;
+    
     // Route to appropriate assertion handler based on collection
     match context.data.collection.as_str() {
         "admins" => {
             assert_admin_operations(context)
         },
+        "admin_emails" => {
+            assert_admin_email_operations(context)
+        },
+        "admin_config" => {
+            assert_admin_config_operations(context)
+        },
+        "roles" => {
+            assert_role_operations(context)
+        },
         "causes" => {
             assert_cause_operations(context)
         },
@@ -123,6 +241,18 @@ fn assert_set_doc(context: AssertSetDocContext) -> std::result::Result<(), Strin
         "allocations" => {
             assert_allocation_operations(context)
         },
+        "matching_campaigns" => {
+            assert_matching_campaign_operations(context)
+        },
+        "cause_categories" => {
+            assert_cause_category_operations(context)
+        },
+        "cause_follows" => {
+            assert_cause_follow_operations(context)
+        },
+        "cause_versions" => {
+            assert_cause_version_operations(context)
+        },
         _ => {
             // Log unknown collection validation attempt
             ic_cdk::println!("Validation attempt on unhandled collection: {}", context.data.collection);
@@ -133,11 +263,22 @@ fn assert_set_doc(context: AssertSetDocContext) -> std::result::Result<(), Strin
 
 #[assert_delete_doc]
 fn assert_delete_doc(context: AssertDeleteDocContext) -> std::result::Result<(), String> {
+    consume_rate_limit(&context.caller, &context.data.collection, "delete")?;
+    
     // Route to appropriate deletion assertion handler based on collection
     match context.data.collection.as_str() {
         "admins" => {
             assert_admin_deletion(context)
         },
+        "admin_emails" => {
+            assert_admin_email_deletion(context)
+        },
+        "admin_config" => {
+            assert_admin_config_deletion(context)
+        },
+        "roles" => {
+            assert_role_deletion(context)
+        },
         "causes" => {
             assert_cause_deletion(context)
         },
@@ -156,6 +297,18 @@ fn assert_delete_doc(context: AssertDeleteDocContext) -> std::result::Result<(),
         "allocations" => {
             assert_allocation_deletion(context)
         },
+        "matching_campaigns" => {
+            assert_matching_campaign_deletion(context)
+        },
+        "cause_categories" => {
+            assert_cause_category_deletion(context)
+        },
+        "cause_follows" => {
+            assert_cause_follow_deletion(context)
+        },
+        "cause_versions" => {
+            assert_cause_version_deletion(context)
+        },
         _ => {
             // Log unknown collection deletion attempt
             ic_cdk::println!("Deletion attempt on unhandled collection: {}", context.data.collection);
@@ -164,6 +317,19 @@ fn assert_delete_doc(context: AssertDeleteDocContext) -> std::result::Result<(),
     }
 }
 
+#[assert_upload_asset]
+fn assert_upload_asset(context: AssertUploadAssetContext) -> std::result::Result<(), String> {
+    // Route to appropriate upload assertion handler based on collection
+    match context.data.batch.key.collection.as_str() {
+        "cause_images" => {
+            assert_cause_image_upload(context)
+        },
+        _ => {
+            Ok(())
+        }
+    }
+}
+
 // Default implementations for other hooks
 #[on_set_many_docs]
 fn on_set_many_docs(_context: OnSetManyDocsContext) -> std::result::Result<(), String> {
@@ -176,7 +342,21 @@ fn on_delete_asset(_context: OnDeleteAssetContext) -> std::result::Result<(), St
 }
 
 #[on_delete_doc]
-fn on_delete_doc(_context: OnDeleteDocContext) -> std::result::Result<(), String> {
+fn on_delete_doc(context: OnDeleteDocContext) -> std::result::Result<(), String> {
+    // Route to appropriate deletion handler based on collection
+    match context.data.collection.as_str() {
+        "admins" => {
+            handle_admin_deletion(context)?
+        },
+        "allocations" => {
+            handle_allocation_deletion(context)?
+        },
+        "cause_follows" => {
+            handle_cause_follow_deletion(context)?
+        },
+        _ => {}
+    }
+    
     Ok(())
 }
 
@@ -205,4 +385,129 @@ fn on_upload_asset(_context: OnUploadAssetContext) -> std::result::Result<(), St
     Ok(())
 }
 
+// Custom endpoints (declared in satellite_extension.did)
+
+// Seed the cause category registry with the built-in categories (admin controllers only)
+#[ic_cdk::update]
+fn seed_cause_categories() -> std::result::Result<u32, String> {
+    if !is_admin_controller_caller(&ic_cdk::api::msg_caller()) {
+        return Err("Only satellite controllers can seed cause categories".into());
+    }
+    
+    seed_default_categories()
+}
+
+// Seed the roles collection with the built-in admin roles (admin controllers only)
+#[ic_cdk::update]
+fn seed_admin_roles() -> std::result::Result<u32, String> {
+    if !is_admin_controller_caller(&ic_cdk::api::msg_caller()) {
+        return Err("Only satellite controllers can seed admin roles".into());
+    }
+    
+    seed_default_roles()
+}
+
+// Recompute every cause's funds raised from allocations (admin controllers only)
+#[ic_cdk::update]
+fn recompute_cause_funds_raised() -> std::result::Result<u32, String> {
+    if !is_admin_controller_caller(&ic_cdk::api::msg_caller()) {
+        return Err("Only satellite controllers can recompute cause funds".into());
+    }
+    
+    recompute_cause_funds()
+}
+
+// Record that the calling admin is active, e.g. when the admin dashboard is opened
+#[ic_cdk::update]
+fn touch_admin_activity() -> std::result::Result<bool, String> {
+    record_admin_activity(&ic_cdk::api::msg_caller())
+}
+
+// Recompute every cause's follower count from cause follows (admin controllers only)
+#[ic_cdk::update]
+fn recompute_cause_follower_counts() -> std::result::Result<u32, String> {
+    if !is_admin_controller_caller(&ic_cdk::api::msg_caller()) {
+        return Err("Only satellite controllers can recompute cause followers".into());
+    }
+    
+    recompute_cause_followers()
+}
+
+// Renumber the public cause listing to remove duplicate sort orders (admin controllers only)
+#[ic_cdk::update]
+fn normalize_cause_listing_order() -> std::result::Result<u32, String> {
+    let caller = ic_cdk::api::msg_caller();
+    if !is_admin_controller_caller(&caller) {
+        return Err("Only satellite controllers can normalize the cause listing order".into());
+    }
+    
+    normalize_cause_sort_orders(&caller)
+}
+
+// Re-seed an active platform admin after a lockout (admin controllers only)
+#[ic_cdk::update]
+fn recover_platform_admin_access(user: candid::Principal, email: String, name: Option<String>) -> std::result::Result<(), String> {
+    let caller = ic_cdk::api::msg_caller();
+    if !is_admin_controller_caller(&caller) {
+        return Err("Only satellite controllers can recover platform admin access".into());
+    }
+    
+    seed_platform_admin(&caller, &user, &email, name, false)
+}
+
+// Create the first platform admin of a satellite with no admin documents (admin controllers only)
+#[ic_cdk::update]
+fn bootstrap_platform_admin(user: candid::Principal, email: String, name: Option<String>) -> std::result::Result<(), String> {
+    let caller = ic_cdk::api::msg_caller();
+    if !is_admin_controller_caller(&caller) {
+        return Err("Only satellite controllers can bootstrap the platform admin".into());
+    }
+    
+    seed_platform_admin(&caller, &user, &email, name, true)
+}
+
+// Move legacy admin documents to their principal key (admin controllers only)
+#[ic_cdk::update]
+fn migrate_admin_document_keys() -> std::result::Result<u32, String> {
+    if !is_admin_controller_caller(&ic_cdk::api::msg_caller()) {
+        return Err("Only satellite controllers can migrate admin documents".into());
+    }
+    
+    migrate_admin_keys()
+}
+
+// Rebuild the admin email index from the admins collection (admin controllers only)
+#[ic_cdk::update]
+fn rebuild_admin_email_index() -> std::result::Result<u32, String> {
+    if !is_admin_controller_caller(&ic_cdk::api::msg_caller()) {
+        return Err("Only satellite controllers can rebuild the admin email index".into());
+    }
+    
+    rebuild_email_index()
+}
+
+// Retire a cause, moving the waqfs that select it to a replacement cause (cause managers only)
+#[ic_cdk::update]
+fn retire_cause_into(cause_id: String, replacement_id: String) -> std::result::Result<u32, String> {
+    retire_cause(&ic_cdk::api::msg_caller(), &cause_id, &replacement_id)
+}
+
+// Reorder the public cause listing from an ordered list of cause IDs (cause managers only)
+#[ic_cdk::update]
+fn reorder_cause_listing(ordered_ids: Vec<String>) -> std::result::Result<u32, String> {
+    reorder_causes(&ic_cdk::api::msg_caller(), &ordered_ids)
+}
+
+// A cause as it stood at a given document version
+#[ic_cdk::query]
+fn get_cause_at_version(cause_id: String, version: u64) -> std::result::Result<Option<CauseVersion>, String> {
+    get_cause_version_at(&ic_cdk::api::msg_caller(), &cause_id, version)
+}
+
+// A cause as it stood at a given time (milliseconds since epoch)
+#[ic_cdk::query]
+fn get_cause_at_time(cause_id: String, timestamp: u64) -> std::result::Result<Option<CauseVersion>, String> {
+    get_cause_version_at_time(&ic_cdk::api::msg_caller(), &cause_id, timestamp)
+}
+
 include_satellite!();
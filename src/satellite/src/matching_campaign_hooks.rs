@@ -0,0 +1,337 @@
+use crate::authz::require_permission;
+use crate::cause_hooks::Cause;
+use crate::store_utils::{get_doc_data, is_satellite_caller, list_doc_data, set_doc_data};
+use crate::waqf_types::{DonationData, WaqfData};
+use serde::{Deserialize, Serialize};
+use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext};
+use junobuild_utils::decode_doc_data;
+
+pub const MATCHING_CAMPAIGNS_COLLECTION: &str = "matching_campaigns";
+const DONATIONS_COLLECTION: &str = "donations";
+const WAQFS_COLLECTION: &str = "waqfs";
+const CAUSES_COLLECTION: &str = "causes";
+
+// Validation constants
+const MAX_MATCH_RATIO: f64 = 10.0;
+const MAX_CAMPAIGN_CAP: f64 = 100_000_000.0;
+const MIN_MATCH_AMOUNT: f64 = 0.01;
+const VALID_CURRENCIES: &[&str] = &["USD", "EUR", "GBP", "SAR", "AED"];
+
+// Matching-gift campaign: a sponsor matches qualifying donations up to a cap
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MatchingCampaign {
+    pub id: String,
+    pub name: String,
+    pub sponsor: String,                 // Sponsor name shown on matched donations
+    #[serde(rename = "eligibleWaqfs")]
+    pub eligible_waqfs: Vec<String>,     // Waqf IDs whose donations qualify
+    #[serde(rename = "eligibleCauses")]
+    pub eligible_causes: Vec<String>,    // Cause IDs; donations to waqfs supporting them qualify
+    #[serde(rename = "matchRatio")]
+    pub match_ratio: f64,                // e.g. 1.0 = one-for-one, 0.5 = fifty cents per unit
+    pub cap: f64,                        // Total sponsor pool
+    pub currency: String,
+    #[serde(rename = "matchedTotal")]
+    pub matched_total: f64,              // Server-maintained, amount of the pool already used
+    #[serde(rename = "startsAt")]
+    pub starts_at: u64,                  // Milliseconds
+    #[serde(rename = "endsAt")]
+    pub ends_at: u64,                    // Milliseconds
+    #[serde(rename = "isActive")]
+    pub is_active: bool,
+    #[serde(rename = "createdBy")]
+    pub created_by: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+}
+
+impl MatchingCampaign {
+    fn remaining(&self) -> f64 {
+        (self.cap - self.matched_total).max(0.0)
+    }
+
+    fn is_running(&self, now: u64) -> bool {
+        self.is_active && now >= self.starts_at && now <= self.ends_at
+    }
+}
+
+// Matching campaign validation function
+fn validate_matching_campaign_data(campaign: &MatchingCampaign) -> std::result::Result<(), String> {
+    // 1. Basic required fields validation
+    if campaign.id.trim().is_empty() {
+        return Err("Campaign ID is required".into());
+    }
+
+    if campaign.name.trim().is_empty() {
+        return Err("Campaign name is required".into());
+    }
+
+    if campaign.name.len() > 200 {
+        return Err("Campaign name must be 200 characters or less".into());
+    }
+
+    if campaign.sponsor.trim().is_empty() {
+        return Err("Campaign sponsor is required".into());
+    }
+
+    if campaign.created_by.trim().is_empty() {
+        return Err("Created by field is required".into());
+    }
+
+    // 2. Eligibility validation
+    if campaign.eligible_waqfs.is_empty() && campaign.eligible_causes.is_empty() {
+        return Err("Campaign must list at least one eligible waqf or cause".into());
+    }
+
+    if campaign.eligible_waqfs.iter().chain(campaign.eligible_causes.iter()).any(|id| id.trim().is_empty()) {
+        return Err("Eligible waqf and cause IDs cannot be empty".into());
+    }
+
+    // 3. Financial validation
+    if !(campaign.match_ratio > 0.0 && campaign.match_ratio <= MAX_MATCH_RATIO) {
+        return Err(format!("Match ratio must be greater than 0 and at most {}", MAX_MATCH_RATIO));
+    }
+
+    if !(campaign.cap > 0.0 && campaign.cap <= MAX_CAMPAIGN_CAP) {
+        return Err(format!("Campaign cap must be greater than 0 and at most {}", MAX_CAMPAIGN_CAP));
+    }
+
+    if campaign.matched_total < 0.0 || campaign.matched_total > campaign.cap {
+        return Err("Matched total must be between 0 and the campaign cap".into());
+    }
+
+    if !VALID_CURRENCIES.contains(&campaign.currency.as_str()) {
+        return Err(format!("Invalid currency: {}. Valid currencies: {}", campaign.currency, VALID_CURRENCIES.join(", ")));
+    }
+
+    // 4. Date window validation
+    if campaign.starts_at == 0 || campaign.ends_at == 0 {
+        return Err("Campaign start and end dates are required".into());
+    }
+
+    if campaign.ends_at <= campaign.starts_at {
+        return Err("Campaign end date must be after its start date".into());
+    }
+
+    if campaign.created_at == 0 {
+        return Err("Created at timestamp is required".into());
+    }
+
+    Ok(())
+}
+
+// The matched total is only ever advanced by the donation hook
+fn validate_matched_total_ownership(
+    campaign: &MatchingCampaign,
+    context: &AssertSetDocContext,
+) -> std::result::Result<(), String> {
+    if is_satellite_caller(&context.caller) {
+        return Ok(());
+    }
+
+    let previous_total = match &context.data.data.current {
+        Some(current_doc) => {
+            let previous: MatchingCampaign = decode_doc_data(&current_doc.data)
+                .map_err(|e| format!("Cannot decode current campaign data: {}", e))?;
+            previous.matched_total
+        },
+        None => 0.0,
+    };
+
+    if (campaign.matched_total - previous_total).abs() > f64::EPSILON {
+        return Err("Matched total is maintained by the platform and cannot be changed".into());
+    }
+
+    Ok(())
+}
+
+// Campaigns commit sponsor funds, so only finance admins may manage them,
+// under their own identity. created_by never changes after creation.
+fn validate_campaign_authorization(campaign: &MatchingCampaign, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    require_permission(&context.caller, "financial_oversight")
+        .map_err(|_| "Only finance admins can manage matching campaigns (requires financial_oversight permission)".to_string())?;
+
+    match &context.data.data.current {
+        None => {
+            if campaign.created_by != context.caller.to_text() {
+                return Err("Created by must be the caller".into());
+            }
+        },
+        Some(current_doc) => {
+            let current: MatchingCampaign = decode_doc_data(&current_doc.data)
+                .map_err(|e| format!("Cannot decode current matching campaign data: {}", e))?;
+            if campaign.created_by != current.created_by {
+                return Err("Created by cannot be changed".into());
+            }
+        },
+    }
+
+    Ok(())
+}
+
+// Newly listed eligible causes must exist
+fn validate_campaign_eligible_causes(campaign: &MatchingCampaign, context: &AssertSetDocContext) -> std::result::Result<(), String> {
+    if is_satellite_caller(&context.caller) {
+        return Ok(());
+    }
+
+    let previous_causes = match &context.data.data.current {
+        Some(current_doc) => {
+            let current: MatchingCampaign = decode_doc_data(&current_doc.data)
+                .map_err(|e| format!("Cannot decode current matching campaign data: {}", e))?;
+            current.eligible_causes
+        },
+        None => Vec::new(),
+    };
+
+    for cause_id in campaign.eligible_causes.iter().filter(|id| !previous_causes.contains(id)) {
+        if get_doc_data::<Cause>(CAUSES_COLLECTION, cause_id)?.is_none() {
+            return Err(format!("Eligible cause {} does not exist", cause_id));
+        }
+    }
+
+    Ok(())
+}
+
+// Main assertion function for matching campaign operations
+pub fn assert_matching_campaign_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
+    let campaign: MatchingCampaign = decode_doc_data(&context.data.data.proposed.data)
+        .map_err(|e| format!("Invalid matching campaign data structure: {}", e))?;
+
+    validate_matching_campaign_data(&campaign)?;
+
+    validate_campaign_authorization(&campaign, &context)?;
+
+    validate_campaign_eligible_causes(&campaign, &context)?;
+
+    validate_matched_total_ownership(&campaign, &context)?;
+
+    ic_cdk::println!(
+        "Matching campaign validation passed: {} - Sponsor: {}, Ratio: {}, Cap: {} {}",
+        campaign.id, campaign.sponsor, campaign.match_ratio, campaign.cap, campaign.currency
+    );
+
+    Ok(())
+}
+
+// Deletion assertion for matching campaigns
+pub fn assert_matching_campaign_deletion(context: AssertDeleteDocContext) -> std::result::Result<(), String> {
+    require_permission(&context.caller, "financial_oversight")
+        .map_err(|_| "Only finance admins can manage matching campaigns (requires financial_oversight permission)".to_string())?;
+
+    let current_doc = context.data.data.current.as_ref()
+        .ok_or("No current document found for deletion")?;
+    let campaign: MatchingCampaign = decode_doc_data(&current_doc.data)
+        .map_err(|e| format!("Cannot decode matching campaign data for deletion: {}", e))?;
+
+    // Matched donations reference the campaign, keep it for the audit trail
+    if campaign.matched_total > 0.0 {
+        return Err("Cannot delete a matching campaign that has already matched donations".into());
+    }
+
+    ic_cdk::println!("Matching campaign deletion: {} - Sponsor: {}", campaign.id, campaign.sponsor);
+
+    Ok(())
+}
+
+// Handle matching campaign changes (logging)
+pub fn handle_matching_campaign_changes(context: OnSetDocContext) -> std::result::Result<(), String> {
+    let campaign: MatchingCampaign = decode_doc_data(&context.data.data.after.data)
+        .map_err(|e| format!("Cannot decode matching campaign data: {}", e))?;
+
+    let operation_type = if context.data.data.before.is_none() {
+        "CREATE"
+    } else {
+        "UPDATE"
+    };
+
+    ic_cdk::println!(
+        "Matching Campaign {}: {} - Sponsor: {}, Matched: {} / {} {}, Active: {}",
+        operation_type,
+        context.data.key,
+        campaign.sponsor,
+        campaign.matched_total,
+        campaign.cap,
+        campaign.currency,
+        campaign.is_active
+    );
+
+    Ok(())
+}
+
+// Check whether a donation qualifies for a campaign
+fn is_donation_eligible(
+    campaign: &MatchingCampaign,
+    donation: &DonationData,
+    waqf: Option<&WaqfData>,
+) -> bool {
+    if campaign.eligible_waqfs.contains(&donation.waqf_id) {
+        return true;
+    }
+
+    waqf.map(|waqf| waqf.selected_causes.iter().any(|cause| campaign.eligible_causes.contains(cause)))
+        .unwrap_or(false)
+}
+
+// Create matched-donation records for a donation that just completed.
+// Each running campaign matches the donation once, until its cap is exhausted.
+pub fn apply_donation_matches(donation: &DonationData) -> std::result::Result<(), String> {
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+
+    let campaigns: Vec<(String, MatchingCampaign)> = list_doc_data::<MatchingCampaign>(MATCHING_CAMPAIGNS_COLLECTION)?
+        .into_iter()
+        .filter(|(_, campaign)| campaign.is_running(now) && campaign.currency == donation.currency && campaign.remaining() >= MIN_MATCH_AMOUNT)
+        .collect();
+
+    if campaigns.is_empty() {
+        return Ok(());
+    }
+
+    let waqf: Option<WaqfData> = get_doc_data(WAQFS_COLLECTION, &donation.waqf_id)?;
+
+    for (campaign_key, mut campaign) in campaigns {
+        if !is_donation_eligible(&campaign, donation, waqf.as_ref()) {
+            continue;
+        }
+
+        let match_id = format!("match_{}_{}", campaign.id, donation.id);
+        if get_doc_data::<DonationData>(DONATIONS_COLLECTION, &match_id)?.is_some() {
+            continue; // Already matched
+        }
+
+        let match_amount = ((donation.amount * campaign.match_ratio).min(campaign.remaining()) * 100.0).floor() / 100.0;
+        if match_amount < MIN_MATCH_AMOUNT {
+            continue;
+        }
+
+        let matched_donation = DonationData {
+            id: match_id.clone(),
+            waqf_id: donation.waqf_id.clone(),
+            date: donation.date.clone(),
+            amount: match_amount,
+            currency: donation.currency.clone(),
+            status: "completed".to_string(),
+            transaction_id: None,
+            donor_name: Some(campaign.sponsor.clone()),
+            matching_campaign_id: Some(campaign.id.clone()),
+            matched_donation_id: Some(donation.id.clone()),
+        };
+
+        set_doc_data(DONATIONS_COLLECTION, &match_id, &matched_donation)?;
+
+        campaign.matched_total += match_amount;
+        set_doc_data(MATCHING_CAMPAIGNS_COLLECTION, &campaign_key, &campaign)?;
+
+        ic_cdk::println!(
+            "MATCHED DONATION: {} matched {} {} for donation {} (campaign {}: {} / {})",
+            campaign.sponsor, match_amount, campaign.currency, donation.id,
+            campaign.id, campaign.matched_total, campaign.cap
+        );
+
+        if campaign.remaining() < MIN_MATCH_AMOUNT {
+            ic_cdk::println!("NOTICE: Matching campaign cap exhausted - {} ({})", campaign.name, campaign.id);
+        }
+    }
+
+    Ok(())
+}
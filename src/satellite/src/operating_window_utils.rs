@@ -0,0 +1,186 @@
+use crate::activity_log_hooks::record_activity_log;
+use crate::admin_config_hooks::ADMIN_CONFIG_COLLECTION;
+use crate::authz::has_permission;
+use crate::store_utils::{get_doc_data, is_satellite_caller};
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Operating windows: when sensitive actions may be performed, per action class.
+// The policy is the "operating_windows" document of the admin_config collection;
+// without a policy (or a window for a class) the action is always allowed.
+
+pub const OPERATING_WINDOWS_KEY: &str = "operating_windows";
+
+// Action classes
+pub const ADMIN_CHANGES: &str = "admin_changes";
+pub const HIGH_VALUE_ALLOCATIONS: &str = "high_value_allocations";
+const ACTION_CLASSES: &[&str] = &[ADMIN_CHANGES, HIGH_VALUE_ALLOCATIONS];
+
+// Holders of this permission may act outside the windows; every use is logged
+pub const EMERGENCY_OVERRIDE_PERMISSION: &str = "emergency_override";
+
+const WEEKDAYS: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+// Allowed window for one action class, in the window's local time
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OperatingWindow {
+    #[serde(rename = "utcOffsetMinutes")]
+    pub utc_offset_minutes: i32,         // Local time zone as a fixed offset, e.g. 180 for Riyadh (UTC+3)
+    pub weekdays: Vec<String>,           // "sun" .. "sat", e.g. ["sun", "mon", "tue", "wed", "thu"]
+    #[serde(rename = "startHour")]
+    pub start_hour: u8,                  // Inclusive, 0-23
+    #[serde(rename = "endHour")]
+    pub end_hour: u8,                    // Exclusive, 1-24
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OperatingWindowsPolicy {
+    pub windows: HashMap<String, OperatingWindow>,  // Action class -> window
+    pub holidays: Vec<String>,                      // Local dates "YYYY-MM-DD" on which windows are closed
+    #[serde(rename = "highValueAllocationThreshold")]
+    pub high_value_allocation_threshold: f64,       // Allocations at or above this amount are high value
+    #[serde(rename = "updatedBy")]
+    pub updated_by: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,                            // Milliseconds
+}
+
+impl OperatingWindow {
+    fn is_open(&self, now_ms: u64, holidays: &[String]) -> bool {
+        let local_ms = now_ms as i64 + self.utc_offset_minutes as i64 * 60_000;
+        let days = local_ms.div_euclid(MS_PER_DAY);
+        let hour = (local_ms.rem_euclid(MS_PER_DAY) / (60 * 60 * 1000)) as u8;
+
+        // 1970-01-01 was a Thursday
+        let weekday = WEEKDAYS[(days + 4).rem_euclid(7) as usize];
+
+        self.weekdays.iter().any(|d| d == weekday)
+            && hour >= self.start_hour
+            && hour < self.end_hour
+            && !holidays.contains(&civil_date(days))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} {:02}:00-{:02}:00 (UTC{}{:02}:{:02})",
+            self.weekdays.join(","), self.start_hour, self.end_hour,
+            if self.utc_offset_minutes < 0 { '-' } else { '+' },
+            self.utc_offset_minutes.abs() / 60, self.utc_offset_minutes.abs() % 60
+        )
+    }
+}
+
+// "YYYY-MM-DD" for a number of days since 1970-01-01 (proleptic Gregorian calendar)
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn is_valid_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    parts.len() == 3
+        && parts[0].len() == 4
+        && parts[1].len() == 2
+        && parts[2].len() == 2
+        && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit()))
+        && matches!(parts[1].parse::<u8>(), Ok(1..=12))
+        && matches!(parts[2].parse::<u8>(), Ok(1..=31))
+}
+
+// Validate a policy document written to admin_config
+pub fn validate_operating_windows_policy(policy: &OperatingWindowsPolicy) -> std::result::Result<(), String> {
+    for (class, window) in &policy.windows {
+        if !ACTION_CLASSES.contains(&class.as_str()) {
+            return Err(format!("Unknown action class: {}. Valid classes: {}", class, ACTION_CLASSES.join(", ")));
+        }
+
+        if window.utc_offset_minutes.abs() > 14 * 60 {
+            return Err(format!("UTC offset for {} must be between -840 and 840 minutes", class));
+        }
+
+        if window.weekdays.is_empty() {
+            return Err(format!("Window for {} must allow at least one weekday", class));
+        }
+
+        if let Some(day) = window.weekdays.iter().find(|d| !WEEKDAYS.contains(&d.as_str())) {
+            return Err(format!("Invalid weekday '{}'. Use: {}", day, WEEKDAYS.join(", ")));
+        }
+
+        if window.start_hour > 23 || window.end_hour > 24 || window.start_hour >= window.end_hour {
+            return Err(format!("Window hours for {} must satisfy 0 <= start < end <= 24", class));
+        }
+    }
+
+    if let Some(date) = policy.holidays.iter().find(|d| !is_valid_date(d)) {
+        return Err(format!("Invalid holiday date '{}'. Use YYYY-MM-DD", date));
+    }
+
+    if policy.high_value_allocation_threshold <= 0.0 {
+        return Err("High value allocation threshold must be greater than 0".into());
+    }
+
+    Ok(())
+}
+
+fn get_policy() -> std::result::Result<Option<OperatingWindowsPolicy>, String> {
+    get_doc_data(ADMIN_CONFIG_COLLECTION, OPERATING_WINDOWS_KEY)
+}
+
+// Whether an allocation amount falls in the high-value class
+pub fn is_high_value_allocation(amount: f64) -> std::result::Result<bool, String> {
+    Ok(get_policy()?
+        .map(|policy| amount >= policy.high_value_allocation_threshold)
+        .unwrap_or(false))
+}
+
+// Reject an action outside its operating window, unless the caller holds the emergency
+// override permission, in which case the override is recorded in the activity logs.
+// Writes issued by the satellite itself are not restricted.
+pub fn validate_operating_window(caller: &Principal, action_class: &str, resource: &str) -> std::result::Result<(), String> {
+    if is_satellite_caller(caller) {
+        return Ok(());
+    }
+
+    let Some(policy) = get_policy()? else {
+        return Ok(());
+    };
+
+    let Some(window) = policy.windows.get(action_class) else {
+        return Ok(());
+    };
+
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    if window.is_open(now, &policy.holidays) {
+        return Ok(());
+    }
+
+    if !has_permission(caller, EMERGENCY_OVERRIDE_PERMISSION)? {
+        return Err(format!(
+            "Outside the operating window for {}: allowed {}, excluding holidays",
+            action_class, window.describe()
+        ));
+    }
+
+    ic_cdk::println!("WARNING: Emergency override of {} window by {} on {}", action_class, caller.to_text(), resource);
+
+    record_activity_log(
+        "emergency_override_used",
+        "audit",
+        "warning",
+        Some(caller.to_text()),
+        format!("Operating window for {} overridden ({} outside {})", action_class, resource, window.describe()),
+        None,
+        Some(resource),
+    )
+}
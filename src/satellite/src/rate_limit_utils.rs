@@ -0,0 +1,175 @@
+use crate::admin_config_hooks::ADMIN_CONFIG_COLLECTION;
+use crate::store_utils::{get_doc_data, is_satellite_caller, set_doc_data};
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Token-bucket rate limiting per caller, collection and action.
+// Buckets are stored as documents of the `rate_limits` collection, which must use
+// stable memory (so buckets survive upgrades) and controller-only read/write rules.
+// Every client write is checked from the assert dispatch, as "<collection>:<action>" with action
+// create, update or delete. Only keys with a default or an override in the "rate_limits"
+// document of the admin_config collection are limited.
+
+pub const RATE_LIMITS_COLLECTION: &str = "rate_limits";
+pub const RATE_LIMITS_KEY: &str = "rate_limits";
+
+// Rate limit for an action: at most `max_tokens` calls per `window_ms`,
+// with one token refilled every `window_ms / max_tokens`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RateLimit {
+    #[serde(rename = "maxTokens")]
+    pub max_tokens: u32,
+    #[serde(rename = "windowMs")]
+    pub window_ms: u64,
+}
+
+// Overrides keyed "<collection>:<action>", e.g. "causes:create"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RateLimitsConfig {
+    pub limits: HashMap<String, RateLimit>,
+    #[serde(rename = "updatedBy")]
+    pub updated_by: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,                 // Milliseconds
+}
+
+const MAX_RATE_LIMIT_TOKENS: u32 = 10_000;
+const MIN_RATE_LIMIT_WINDOW_MS: u64 = 1000;
+const MAX_RATE_LIMIT_WINDOW_MS: u64 = 7 * DAY_MS;
+
+impl RateLimit {
+    fn refill_interval_ms(&self) -> u64 {
+        (self.window_ms / self.max_tokens.max(1) as u64).max(1)
+    }
+}
+
+const HOUR_MS: u64 = 60 * 60 * 1000;
+const DAY_MS: u64 = 24 * HOUR_MS;
+
+// Actions a limit can apply to
+const RATE_LIMITED_ACTIONS: &[&str] = &["create", "update", "delete"];
+
+// Default limits as (collection, action, limit). Actions without an entry are not limited.
+const RATE_LIMITS: &[(&str, &str, RateLimit)] = &[
+    ("causes", "create", RateLimit { max_tokens: 5, window_ms: HOUR_MS }),
+    ("causes", "update", RateLimit { max_tokens: 60, window_ms: HOUR_MS }),
+    ("admin_requests", "create", RateLimit { max_tokens: 10, window_ms: DAY_MS }),
+];
+
+// Persisted state of one bucket
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RateBucket {
+    pub tokens: u32,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,                 // Milliseconds, time of the last refill
+}
+
+fn limit_key(collection: &str, action: &str) -> String {
+    format!("{}:{}", collection, action)
+}
+
+// Effective limit of an action: the configured override, else the default
+fn find_rate_limit(collection: &str, action: &str) -> std::result::Result<Option<RateLimit>, String> {
+    let config: Option<RateLimitsConfig> = get_doc_data(ADMIN_CONFIG_COLLECTION, RATE_LIMITS_KEY)?;
+
+    if let Some(limit) = config.and_then(|mut config| config.limits.remove(&limit_key(collection, action))) {
+        return Ok(Some(limit));
+    }
+
+    Ok(RATE_LIMITS.iter()
+        .find(|(c, a, _)| *c == collection && *a == action)
+        .map(|(_, _, limit)| limit.clone()))
+}
+
+// "<collection>:<action>" with a collection name and a rate limited action
+fn is_valid_limit_key(key: &str) -> bool {
+    let Some((collection, action)) = key.split_once(':') else {
+        return false;
+    };
+
+    !collection.is_empty()
+        && collection.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+        && RATE_LIMITED_ACTIONS.contains(&action)
+}
+
+// Validate an overrides document written to admin_config
+pub fn validate_rate_limits_config(config: &RateLimitsConfig) -> std::result::Result<(), String> {
+    for (key, limit) in &config.limits {
+        if !is_valid_limit_key(key) {
+            return Err(format!(
+                "Invalid rate limit key: {}. Use <collection>:<action> with action one of: {}",
+                key, RATE_LIMITED_ACTIONS.join(", ")
+            ));
+        }
+
+        if limit.max_tokens == 0 || limit.max_tokens > MAX_RATE_LIMIT_TOKENS {
+            return Err(format!("Max tokens for {} must be between 1 and {}", key, MAX_RATE_LIMIT_TOKENS));
+        }
+
+        if !(MIN_RATE_LIMIT_WINDOW_MS..=MAX_RATE_LIMIT_WINDOW_MS).contains(&limit.window_ms) {
+            return Err(format!(
+                "Window for {} must be between {} and {} ms",
+                key, MIN_RATE_LIMIT_WINDOW_MS, MAX_RATE_LIMIT_WINDOW_MS
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn bucket_key(caller: &Principal, collection: &str, action: &str) -> String {
+    format!("{}:{}:{}", collection, action, caller.to_text())
+}
+
+fn format_duration(ms: u64) -> String {
+    let seconds = ms.div_ceil(1000);
+    match seconds {
+        0..=59 => format!("{} second(s)", seconds),
+        60..=3599 => format!("{} minute(s)", seconds.div_ceil(60)),
+        _ => format!("{} hour(s)", seconds.div_ceil(3600)),
+    }
+}
+
+// Take a token from the caller's bucket for this collection and action,
+// or fail with a retry-after message when the bucket is empty.
+// Writes issued by the satellite itself are never limited.
+pub fn consume_rate_limit(caller: &Principal, collection: &str, action: &str) -> std::result::Result<(), String> {
+    if is_satellite_caller(caller) {
+        return Ok(());
+    }
+
+    let Some(limit) = find_rate_limit(collection, action)? else {
+        return Ok(());
+    };
+
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    let key = bucket_key(caller, collection, action);
+    let interval = limit.refill_interval_ms();
+
+    let mut bucket = get_doc_data::<RateBucket>(RATE_LIMITS_COLLECTION, &key)?
+        .unwrap_or(RateBucket { tokens: limit.max_tokens, updated_at: now });
+
+    // Refill the tokens earned since the last refill, capped at the bucket size
+    let earned = now.saturating_sub(bucket.updated_at) / interval;
+    if earned > 0 {
+        bucket.tokens = (bucket.tokens as u64 + earned).min(limit.max_tokens as u64) as u32;
+        bucket.updated_at += earned * interval;
+    }
+
+    if bucket.tokens == 0 {
+        let retry_after = (bucket.updated_at + interval).saturating_sub(now);
+        return Err(format!(
+            "Rate limit exceeded for {} on {} (max {} per {}). Retry after {}.",
+            action, collection, limit.max_tokens, format_duration(limit.window_ms), format_duration(retry_after)
+        ));
+    }
+
+    // A full bucket does not accumulate time towards the next refill
+    if bucket.tokens >= limit.max_tokens {
+        bucket.updated_at = now;
+    }
+    bucket.tokens -= 1;
+
+    set_doc_data(RATE_LIMITS_COLLECTION, &key, &bucket)
+}
@@ -0,0 +1,250 @@
+use crate::admin_hooks::{set_admin_data, AdminUser};
+use crate::authz::{require_permission, ADMINS_COLLECTION};
+use crate::store_utils::{get_doc_data, list_doc_data, set_doc_data};
+use serde::{Deserialize, Serialize};
+use junobuild_satellite::{AssertSetDocContext, AssertDeleteDocContext, OnSetDocContext};
+use junobuild_utils::decode_doc_data;
+
+// Admin roles and their permission sets. Documents are keyed by role name.
+pub const ROLES_COLLECTION: &str = "roles";
+
+// Built-in role names
+pub const SUPPORT_AGENT_ROLE: &str = "support_agent";
+pub const CONTENT_MODERATOR_ROLE: &str = "content_moderator";
+pub const WAQF_MANAGER_ROLE: &str = "waqf_manager";
+pub const FINANCE_OFFICER_ROLE: &str = "finance_officer";
+pub const COMPLIANCE_OFFICER_ROLE: &str = "compliance_officer";
+pub const PLATFORM_ADMIN_ROLE: &str = "platform_admin";
+
+// Every permission the satellite knows about; roles can only grant these
+pub const KNOWN_PERMISSIONS: &[&str] = &[
+    "platform_governance", "system_administration", "audit_compliance", "financial_oversight",
+    "waqf_management", "cause_management", "cause_approval", "content_moderation", "user_support",
+    "admin_request_creation", "admin_request_approval", "emergency_override",
+];
+
+// Permissions the platform admin role can never lose
+pub const PLATFORM_ADMIN_REQUIRED_PERMISSIONS: &[&str] = &["platform_governance", "admin_request_approval"];
+
+// Built-in roles, used to seed the collection and until it is seeded
+const BUILT_IN_ROLES: &[(&str, &str, &[&str])] = &[
+    (SUPPORT_AGENT_ROLE, "Support Agent", &["user_support"]),
+    (CONTENT_MODERATOR_ROLE, "Content Moderator", &["content_moderation", "user_support"]),
+    (WAQF_MANAGER_ROLE, "Waqf Manager", &["waqf_management", "cause_management"]),
+    (FINANCE_OFFICER_ROLE, "Finance Officer", &["financial_oversight", "audit_compliance"]),
+    (COMPLIANCE_OFFICER_ROLE, "Compliance Officer", &["audit_compliance", "financial_oversight", "cause_approval", "admin_request_creation"]),
+    (PLATFORM_ADMIN_ROLE, "Platform Admin", &["platform_governance", "system_administration", "audit_compliance", "financial_oversight", "waqf_management", "cause_management", "cause_approval", "content_moderation", "user_support", "admin_request_creation", "admin_request_approval", "emergency_override"]),
+];
+
+const MAX_ROLE_NAME_LENGTH: usize = 50;
+const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RoleDefinition {
+    pub name: String,                    // Also the document key, e.g. "auditor_readonly"
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    pub permissions: Vec<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,                 // Milliseconds
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,                 // Milliseconds
+}
+
+fn is_built_in_role(name: &str) -> bool {
+    BUILT_IN_ROLES.iter().any(|(role, _, _)| *role == name)
+}
+
+fn is_valid_role_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_ROLE_NAME_LENGTH
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !name.starts_with('_')
+        && !name.ends_with('_')
+}
+
+// Permission set of a role, or None if the role is not defined.
+// Built-in roles fall back to their default permissions until the collection is seeded.
+pub fn get_role_permissions(role: &str) -> std::result::Result<Option<Vec<String>>, String> {
+    let definition: Option<RoleDefinition> = get_doc_data(ROLES_COLLECTION, role)?;
+
+    if let Some(definition) = definition {
+        return Ok(Some(definition.permissions));
+    }
+
+    Ok(BUILT_IN_ROLES.iter()
+        .find(|(name, _, _)| *name == role)
+        .map(|(_, _, permissions)| permissions.iter().map(|p| p.to_string()).collect()))
+}
+
+// Role validation function
+fn validate_role_data(role: &RoleDefinition, key: &str) -> std::result::Result<(), String> {
+    // 1. Name validation
+    if !is_valid_role_name(&role.name) {
+        return Err(format!(
+            "Invalid role name '{}'. Use lowercase letters, digits and underscores (max {} characters)",
+            role.name, MAX_ROLE_NAME_LENGTH
+        ));
+    }
+
+    if role.name != key {
+        return Err("Role name must match the document key".into());
+    }
+
+    if role.display_name.trim().is_empty() || role.display_name.len() > MAX_DISPLAY_NAME_LENGTH {
+        return Err(format!("Role display name must be between 1 and {} characters", MAX_DISPLAY_NAME_LENGTH));
+    }
+
+    // 2. Permission validation
+    if role.permissions.is_empty() {
+        return Err("A role must grant at least one permission".into());
+    }
+
+    if let Some(unknown) = role.permissions.iter().find(|p| !KNOWN_PERMISSIONS.contains(&p.as_str())) {
+        return Err(format!("Unknown permission '{}'. Known permissions: {}", unknown, KNOWN_PERMISSIONS.join(", ")));
+    }
+
+    // 3. The platform admin role keeps the permissions governance depends on
+    if role.name == PLATFORM_ADMIN_ROLE {
+        for required in PLATFORM_ADMIN_REQUIRED_PERMISSIONS {
+            if !role.permissions.iter().any(|p| p == required) {
+                return Err(format!("The platform_admin role must keep permission: {}", required));
+            }
+        }
+    }
+
+    // 4. Timestamp validation
+    if role.created_at == 0 || role.updated_at == 0 {
+        return Err("Role timestamps are required".into());
+    }
+
+    Ok(())
+}
+
+// Main assertion function for role operations
+pub fn assert_role_operations(context: AssertSetDocContext) -> std::result::Result<(), String> {
+    let role: RoleDefinition = decode_doc_data(&context.data.data.proposed.data)
+        .map_err(|e| format!("Invalid role data structure: {}", e))?;
+
+    validate_role_data(&role, &context.data.key)?;
+
+    require_permission(&context.caller, "platform_governance")
+        .map_err(|_| "Only platform admins can manage roles (requires platform_governance permission)".to_string())?;
+
+    // Effective permissions before this write (built-in roles fall back to their defaults)
+    if let Some(previous_permissions) = get_role_permissions(&role.name)? {
+        remove_dropped_permissions(&role, &previous_permissions)?;
+    }
+
+    ic_cdk::println!("Role validation passed: {} - Permissions: {:?}", role.name, role.permissions);
+
+    Ok(())
+}
+
+// Take permissions a role no longer grants away from the admins holding it, so their
+// documents stay valid against the role. Done in the assertion so there is no window where
+// admin updates fail; an admin document that cannot be updated is logged and skipped.
+fn remove_dropped_permissions(role: &RoleDefinition, previous_permissions: &[String]) -> std::result::Result<(), String> {
+    let dropped: Vec<&String> = previous_permissions.iter()
+        .filter(|p| !role.permissions.contains(p))
+        .collect();
+    if dropped.is_empty() {
+        return Ok(());
+    }
+
+    let admins: Vec<(String, AdminUser)> = list_doc_data(ADMINS_COLLECTION)?;
+    for (key, admin) in admins {
+        if admin.role != role.name || !admin.permissions.iter().any(|p| dropped.contains(&p)) {
+            continue;
+        }
+
+        let updated = AdminUser {
+            permissions: admin.permissions.iter().filter(|p| !dropped.contains(p)).cloned().collect(),
+            ..admin
+        };
+
+        if let Err(e) = set_admin_data(&key, &updated) {
+            ic_cdk::println!("WARNING: Admin {} keeps permissions dropped from role {}: {}", key, role.name, e);
+        }
+    }
+
+    ic_cdk::println!("Role {} dropped permissions {:?} - removed from its admins", role.name, dropped);
+
+    Ok(())
+}
+
+// Deletion assertion for roles
+pub fn assert_role_deletion(context: AssertDeleteDocContext) -> std::result::Result<(), String> {
+    require_permission(&context.caller, "platform_governance")
+        .map_err(|_| "Only platform admins can manage roles (requires platform_governance permission)".to_string())?;
+
+    let name = &context.data.key;
+    if is_built_in_role(name) {
+        return Err(format!("Built-in role '{}' cannot be deleted", name));
+    }
+
+    // A role still held by an admin cannot be removed
+    let admins: Vec<(String, AdminUser)> = list_doc_data(ADMINS_COLLECTION)?;
+    let holders: Vec<String> = admins.into_iter()
+        .filter(|(_, admin)| &admin.role == name)
+        .map(|(key, _)| key)
+        .collect();
+
+    if !holders.is_empty() {
+        return Err(format!(
+            "Cannot delete role '{}' - it is held by {} admin(s): {}",
+            name, holders.len(), holders.join(", ")
+        ));
+    }
+
+    ic_cdk::println!("Role deletion: {}", name);
+
+    Ok(())
+}
+
+// Handle role changes (logging)
+pub fn handle_role_changes(context: OnSetDocContext) -> std::result::Result<(), String> {
+    let role: RoleDefinition = decode_doc_data(&context.data.data.after.data)
+        .map_err(|e| format!("Cannot decode role data: {}", e))?;
+
+    let operation_type = if context.data.data.before.is_none() {
+        "CREATE"
+    } else {
+        "UPDATE"
+    };
+
+    ic_cdk::println!(
+        "IMPORTANT: Role {}: {} by {} - Permissions: {:?}",
+        operation_type, role.name, context.caller.to_text(), role.permissions
+    );
+
+    Ok(())
+}
+
+// Seed the collection with the built-in roles, skipping any that already exist.
+// Returns the number of roles created.
+pub fn seed_default_roles() -> std::result::Result<u32, String> {
+    let now = ic_cdk::api::time() / 1_000_000; // Convert to milliseconds
+    let mut created = 0;
+
+    for (name, display_name, permissions) in BUILT_IN_ROLES {
+        if get_doc_data::<RoleDefinition>(ROLES_COLLECTION, name)?.is_some() {
+            continue;
+        }
+
+        let role = RoleDefinition {
+            name: name.to_string(),
+            display_name: display_name.to_string(),
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+            created_at: now,
+            updated_at: now,
+        };
+
+        set_doc_data(ROLES_COLLECTION, name, &role)?;
+        created += 1;
+    }
+
+    ic_cdk::println!("Roles seeded: {} created", created);
+
+    Ok(created)
+}
@@ -0,0 +1,109 @@
+use candid::Principal;
+use serde::{de::DeserializeOwned, Serialize};
+use junobuild_satellite::{delete_doc_store, get_controllers, get_doc_store, id, list_docs_store, set_doc_store, DelDoc, DocContext, DocUpsert, SetDoc};
+use junobuild_shared::controllers::is_admin_controller;
+use junobuild_shared::types::list::{ListMatcher, ListParams};
+use junobuild_utils::{decode_doc_data, encode_doc_data};
+
+// Shared helpers for reading and writing datastore documents from within hooks.
+// All calls are made as the satellite itself, so collection read/write rules
+// do not restrict server-maintained fields.
+
+// Whether the caller is the satellite (i.e. a write issued by one of our own hooks)
+pub fn is_satellite_caller(caller: &Principal) -> bool {
+    *caller == id()
+}
+
+// Whether the caller is an admin controller of the satellite (used to guard custom endpoints)
+pub fn is_admin_controller_caller(caller: &Principal) -> bool {
+    is_admin_controller(*caller, &get_controllers())
+}
+
+// Read and decode a single document, returning None if it does not exist
+pub fn get_doc_data<T: DeserializeOwned>(collection: &str, key: &str) -> std::result::Result<Option<T>, String> {
+    let doc = get_doc_store(id(), collection.to_string(), key.to_string())?;
+
+    match doc {
+        Some(doc) => {
+            let data: T = decode_doc_data(&doc.data)
+                .map_err(|e| format!("Cannot decode {} document {}: {}", collection, key, e))?;
+            Ok(Some(data))
+        },
+        None => Ok(None),
+    }
+}
+
+// List and decode every document of a collection
+pub fn list_doc_data<T: DeserializeOwned>(collection: &str) -> std::result::Result<Vec<(String, T)>, String> {
+    list_docs_with_params(collection, &Default::default())
+}
+
+// List and decode the documents of a collection whose key starts with a prefix
+pub fn list_doc_data_with_prefix<T: DeserializeOwned>(collection: &str, prefix: &str) -> std::result::Result<Vec<(String, T)>, String> {
+    let params = ListParams {
+        matcher: Some(ListMatcher {
+            key: Some(format!("^{}", escape_regex(prefix))),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    list_docs_with_params(collection, &params)
+}
+
+// Escape a literal for use in a key matcher regex
+fn escape_regex(literal: &str) -> String {
+    let mut escaped = String::with_capacity(literal.len());
+    for c in literal.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn list_docs_with_params<T: DeserializeOwned>(collection: &str, params: &ListParams) -> std::result::Result<Vec<(String, T)>, String> {
+    let results = list_docs_store(id(), collection.to_string(), params)?;
+
+    let mut items = Vec::with_capacity(results.items.len());
+    for (key, doc) in results.items {
+        let data: T = decode_doc_data(&doc.data)
+            .map_err(|e| format!("Cannot decode {} document {}: {}", collection, key, e))?;
+        items.push((key, data));
+    }
+
+    Ok(items)
+}
+
+// Create or overwrite a document, carrying over the current version and description
+pub fn set_doc_data<T: Serialize>(collection: &str, key: &str, data: &T) -> std::result::Result<(), String> {
+    set_doc_data_upsert(collection, key, data)?;
+
+    Ok(())
+}
+
+// Same as set_doc_data, returning the before/after documents for hooks that need them
+// (writes made here do not trigger on_set_doc)
+pub fn set_doc_data_upsert<T: Serialize>(collection: &str, key: &str, data: &T) -> std::result::Result<DocContext<DocUpsert>, String> {
+    let current = get_doc_store(id(), collection.to_string(), key.to_string())?;
+
+    let doc = SetDoc {
+        data: encode_doc_data(data)?,
+        description: current.as_ref().and_then(|doc| doc.description.clone()),
+        version: current.as_ref().and_then(|doc| doc.version),
+    };
+
+    set_doc_store(id(), collection.to_string(), key.to_string(), doc)
+}
+
+// Delete a document if it exists
+pub fn delete_doc_data(collection: &str, key: &str) -> std::result::Result<(), String> {
+    let current = get_doc_store(id(), collection.to_string(), key.to_string())?;
+
+    if let Some(doc) = current {
+        delete_doc_store(id(), collection.to_string(), key.to_string(), DelDoc { version: doc.version })?;
+    }
+
+    Ok(())
+}
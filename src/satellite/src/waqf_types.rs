@@ -74,6 +74,8 @@ pub struct DonationData {
     pub status: String, // "completed" | "pending" | "failed"
     pub transaction_id: Option<String>,
     pub donor_name: Option<String>,
+    pub matching_campaign_id: Option<String>, // Set on matched-donation records created by a campaign
+    pub matched_donation_id: Option<String>, // Donation that triggered the match
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
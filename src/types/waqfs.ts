@@ -45,7 +45,8 @@ export interface Cause {
    */
   icon: string;
   /**
-   * Cover image URL for the cause (optional).
+   * Cover image for the cause (optional): an external image URL or a
+   * `/cause_images/...` asset path uploaded to the satellite.
    */
   coverImage?: string;
   /**
@@ -57,19 +58,32 @@ export interface Cause {
    */
   isActive: boolean;
   /**
-   * Status of the cause (pending, approved, rejected)
+   * Status of the cause (pending, approved, rejected, suspended)
    */
-  status: 'pending' | 'approved' | 'rejected';
+  status: 'pending' | 'approved' | 'rejected' | 'suspended';
   /**
-   * Sort order of the cause.
+   * Principal of the admin who last reviewed the cause (required once it leaves pending).
+   */
+  reviewedBy?: string;
+  /**
+   * Timestamp of the last review (required once it leaves pending).
+   */
+  reviewedAt?: string;
+  /**
+   * Reviewer notes explaining the decision (required for rejected and suspended causes).
+   */
+  reviewNotes?: string;
+  /**
+   * Sort order of the cause. Unique among active approved causes; use the
+   * `reorder_cause_listing` satellite method to reorder the listing.
    */
   sortOrder: number;
   /**
-   * Number of supporters following this cause
+   * Number of supporters following this cause (maintained from cause_follows, read-only)
    */
   followers: number;
   /**
-   * Total funds raised for this cause
+   * Total funds raised for this cause (maintained from allocations, read-only)
    */
   fundsRaised: number;
   /**